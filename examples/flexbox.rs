@@ -57,6 +57,8 @@ fn c_red(s: &mut Style) {
 
 fn c_green(s: &mut Style) {
     s.color = Color::GREEN;
+    s.border = Border::all(Val::Px(4.0), Color::DARK_GRAY);
+    s.shadow = Some(Shadow::new(Vec2::new(4.0, 6.0), 8.0, 0.0, Color::new(0.0, 0.0, 0.0, 0.5)));
     s.width = Val::Px(128.0);
     s.height = Val::Px(128.0);
     s.justify = Justify::Center;
@@ -66,6 +68,10 @@ fn c_green(s: &mut Style) {
 
 fn c_blue(s: &mut Style) {
     s.color = Color::BLUE;
+    s.brush = Some(Brush::linear(Vec2::ZERO, Vec2::new(0.0, 1.0), [
+        ColorStop::new(0.0, Color::LIGHT_BLUE),
+        ColorStop::new(1.0, Color::BLUE)
+    ]));
     s.width = Val::Px(128.0);  
     s.height = Val::Px(128.0);
     s.padding.left = Val::Px(32.0);
@@ -0,0 +1,65 @@
+use gewy::*;
+use gewy::dsl::*;
+use gewy::winit::*;
+use pollster::FutureExt;
+
+const FONT: &[u8] = include_bytes!("../assets/fonts/DejaVuSans.ttf");
+
+fn main() {
+    let font = Font::from_bytes(FONT).unwrap();
+    let root = Node::from_widget(Root { font });
+    let gewy = Gewy::new(root);
+    WinitApp::new(gewy, 512, 512, BackendKind::Wgpu)
+        .start()
+        .block_on();
+}
+
+pub struct Root {
+    font: Font
+}
+
+impl Widget for Root {
+
+    fn style(&self, s: &mut Style) {
+        s.color = Color::DARK_GRAY;
+        s.direction = Direction::Column;
+        s.justify = Justify::Center;
+        s.align = Align::Center;
+    }
+
+    fn descendants(&self, d: &mut Descendants) {
+        text(c_title, Text::new("Hello, gewy!", self.font.clone(), 32.0), d);
+        text((), Text::new("Text is measured by the layout,\nand painted from a glyph atlas.", self.font.clone(), 16.0), d);
+        let paragraph = Paragraph::new("Paragraphs wrap to the width they are given, ", self.font.clone(), 16.0)
+            .with_span(Span::new("with rich spans ", self.font.clone(), 20.0).with_color(Color::LIGHT_BLUE))
+            .with_span(Span::new("that mix sizes and colors. Lines are justified, and cut off with an ellipsis once there are too many of them.", self.font.clone(), 16.0))
+            .with_align(TextAlign::Justify)
+            .with_line_height(1.25)
+            .with_max_lines(4);
+        rich_text(c_paragraph, paragraph, d);
+        let shaped = Paragraph::new("Shaped and reordered: مرحبا بالعالم and שלום עולם.", self.font.clone(), 16.0);
+        rich_text(c_paragraph, shaped, d);
+        text_field(c_field, TextField::new("", self.font.clone(), 16.0).with_placeholder("Type here..."), d);
+        text_field(c_field, TextField::new("", self.font.clone(), 16.0).with_password().with_placeholder("Password"), d);
+        text_field(c_field, TextField::new("Multiple lines\nof text", self.font.clone(), 16.0).with_multi_line(3), d);
+    }
+
+    fn paint(&self, style: &Style, painter: &mut Painter, canvas: Canvas) {
+        util::paint_pane(style, painter, canvas);
+    }
+}
+
+fn c_title(s: &mut Style) {
+    s.color = Color::LIGHT_BLUE;
+    s.margin.bottom = Val::Px(16.0);
+}
+
+fn c_paragraph(s: &mut Style) {
+    s.width = Val::Pc(0.6);
+    s.margin.top = Val::Px(16.0);
+}
+
+fn c_field(s: &mut Style) {
+    s.width = Val::Pc(0.6);
+    s.margin.top = Val::Px(8.0);
+}
@@ -0,0 +1,172 @@
+use crate::{Color, Vec2};
+
+/// Determines how the inside of a shape is filled.
+/// Gradient coordinates are in pixels, relative to the [`crate::Painter`]'s location when the brush was set.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Brush {
+    /// A single flat color.
+    Solid(Color),
+    /// Colors blended along a line.
+    Linear(LinearGradient),
+    /// Colors blended outwards from a center point.
+    Radial(RadialGradient)
+}
+
+impl Brush {
+
+    /// Linear gradient from start to end.
+    pub fn linear(start: Vec2, end: Vec2, stops: impl Into<Vec<ColorStop>>) -> Self {
+        Self::Linear(LinearGradient::new(start, end, stops))
+    }
+
+    /// Radial gradient with a center and radius.
+    pub fn radial(center: Vec2, radius: Vec2, stops: impl Into<Vec<ColorStop>>) -> Self {
+        Self::Radial(RadialGradient::new(center, radius, stops))
+    }
+
+    /// Multiplies the coordinates of the brush by a size.
+    /// Useful for turning a brush with relative coordinates (0.0 - 1.0) into one that covers an area.
+    pub fn scaled(self, size: Vec2) -> Self {
+        match self {
+            Self::Solid(color) => Self::Solid(color),
+            Self::Linear(gradient) => Self::Linear(LinearGradient {
+                start: gradient.start * size,
+                end: gradient.end * size,
+                stops: gradient.stops
+            }),
+            Self::Radial(gradient) => Self::Radial(RadialGradient {
+                center: gradient.center * size,
+                radius: gradient.radius * size,
+                stops: gradient.stops
+            })
+        }
+    }
+
+    /// Moves the coordinates of the brush.
+    pub fn translated(self, offset: Vec2) -> Self {
+        match self {
+            Self::Solid(color) => Self::Solid(color),
+            Self::Linear(gradient) => Self::Linear(LinearGradient {
+                start: gradient.start + offset,
+                end: gradient.end + offset,
+                stops: gradient.stops
+            }),
+            Self::Radial(gradient) => Self::Radial(RadialGradient {
+                center: gradient.center + offset,
+                radius: gradient.radius,
+                stops: gradient.stops
+            })
+        }
+    }
+}
+
+impl Default for Brush {
+    fn default() -> Self {
+        Self::Solid(Color::default())
+    }
+}
+
+impl From<Color> for Brush {
+    fn from(color: Color) -> Self {
+        Self::Solid(color)
+    }
+}
+
+impl From<LinearGradient> for Brush {
+    fn from(gradient: LinearGradient) -> Self {
+        Self::Linear(gradient)
+    }
+}
+
+impl From<RadialGradient> for Brush {
+    fn from(gradient: RadialGradient) -> Self {
+        Self::Radial(gradient)
+    }
+}
+
+/// A color at a position along a gradient.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ColorStop {
+    /// Position along the gradient (0.0 - 1.0).
+    pub offset: f32,
+    pub color: Color
+}
+
+impl ColorStop {
+    pub fn new(offset: f32, color: Color) -> Self {
+        Self { offset, color }
+    }
+}
+
+impl From<(f32, Color)> for ColorStop {
+    fn from((offset, color): (f32, Color)) -> Self {
+        Self::new(offset, color)
+    }
+}
+
+/// Gradient where colors are blended along the line from start to end.
+#[derive(Clone, PartialEq, Debug)]
+pub struct LinearGradient {
+    pub start: Vec2,
+    pub end: Vec2,
+    pub stops: Vec<ColorStop>
+}
+
+impl LinearGradient {
+    pub fn new(start: Vec2, end: Vec2, stops: impl Into<Vec<ColorStop>>) -> Self {
+        Self { start, end, stops: sorted(stops.into()) }
+    }
+}
+
+/// Gradient where colors are blended outwards from a center to an elliptical edge.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RadialGradient {
+    pub center: Vec2,
+    /// Horizontal and vertical radius of the edge.
+    pub radius: Vec2,
+    pub stops: Vec<ColorStop>
+}
+
+impl RadialGradient {
+    pub fn new(center: Vec2, radius: Vec2, stops: impl Into<Vec<ColorStop>>) -> Self {
+        Self { center, radius, stops: sorted(stops.into()) }
+    }
+}
+
+fn sorted(mut stops: Vec<ColorStop>) -> Vec<ColorStop> {
+    stops.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+    stops
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Brush, ColorStop, Color, Vec2};
+
+    #[test]
+    fn test_gradient_stops() {
+        let stops = [(1.0, Color::BLUE), (0.0, Color::RED), (0.5, Color::GREEN)];
+        let expected = vec![
+            ColorStop::new(0.0, Color::RED),
+            ColorStop::new(0.5, Color::GREEN),
+            ColorStop::new(1.0, Color::BLUE)
+        ];
+
+        // Stops are sorted by offset, and kept when moving the gradient.
+        let linear = Brush::linear(Vec2::ZERO, Vec2::X, stops.map(ColorStop::from))
+            .scaled(Vec2::new(10.0, 20.0))
+            .translated(Vec2::new(1.0, 2.0));
+        let Brush::Linear(linear) = linear else { panic!("Expected a linear gradient") };
+        assert_eq!(expected, linear.stops);
+        assert_eq!(Vec2::new(1.0, 2.0), linear.start);
+        assert_eq!(Vec2::new(11.0, 2.0), linear.end);
+
+        // Radial gradients only move their center.
+        let radial = Brush::radial(Vec2::splat(0.5), Vec2::splat(0.5), stops.map(ColorStop::from))
+            .scaled(Vec2::new(10.0, 20.0))
+            .translated(Vec2::new(1.0, 2.0));
+        let Brush::Radial(radial) = radial else { panic!("Expected a radial gradient") };
+        assert_eq!(expected, radial.stops);
+        assert_eq!(Vec2::new(6.0, 12.0), radial.center);
+        assert_eq!(Vec2::new(5.0, 10.0), radial.radius);
+    }
+}
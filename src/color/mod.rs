@@ -0,0 +1,332 @@
+mod parse;
+
+use std::ops::{Add, Sub, Mul, Div};
+
+use bytemuck::{Pod, Zeroable};
+
+
+/// Color in the sRGB color space, with straight (non-premultiplied) alpha.
+/// Backends are responsible for converting to linear or premultiplied colors as needed.
+#[repr(C)]
+#[derive(Pod, Zeroable, Copy, Clone, PartialEq, Debug)]
+pub struct Color { r: f32, g: f32, b: f32, a: f32 }
+impl Color {
+    pub const BLACK: Color = Color::new(0.0, 0.0, 0.0, 1.0);
+    pub const WHITE: Color = Color::new(1.0, 1.0, 1.0, 1.0);
+    pub const DARK_GRAY: Color = Color::new(0.25, 0.25, 0.25, 1.0);
+    pub const GRAY: Color = Color::new(0.5, 0.5, 0.5, 1.0);
+    pub const LIGHT_GRAY: Color = Color::new(0.75, 0.75, 0.75, 1.0);
+    pub const RED: Color = Color::new(1.0, 0.0, 0.0, 1.0);
+    pub const GREEN: Color = Color::new(0.0, 1.0, 0.0, 1.0);
+    pub const BLUE: Color = Color::new(0.0, 0.0, 1.0, 1.0);
+    pub const LIGHT_BLUE: Color = Color::new(0.5, 0.5, 1.0, 1.0);
+    pub const YELLOW: Color = Color::new(1.0, 1.0, 0.0, 1.0);
+    pub const PINK: Color = Color::new(1.0, 0.0, 1.0, 1.0);
+    pub const TEAL: Color = Color::new(0.0, 1.0, 1.0, 1.0);
+    pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// Multiplies the color channels by alpha.
+    /// Premultiplied colors blend and interpolate without dark fringes.
+    pub fn premultiply(self) -> Self {
+        Self::new(self.r * self.a, self.g * self.a, self.b * self.a, self.a)
+    }
+
+    /// Divides the color channels by alpha, reversing [`Color::premultiply`].
+    /// Fully transparent colors become transparent black.
+    pub fn unpremultiply(self) -> Self {
+        if self.a <= 0.0 {
+            return Self::new(0.0, 0.0, 0.0, 0.0);
+        }
+        Self::new(self.r / self.a, self.g / self.a, self.b / self.a, self.a)
+    }
+
+    /// Creates a color from 8-bit channels.
+    pub fn from_rgba8(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self::new(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0, a as f32 / 255.0)
+    }
+
+    /// Converts to 8-bit channels, clamping out-of-range values.
+    pub fn to_rgba8(self) -> [u8; 4] {
+        let channel = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+        [channel(self.r), channel(self.g), channel(self.b), channel(self.a)]
+    }
+
+    /// Formats as a hex string like "#rrggbbaa".
+    pub fn to_hex(self) -> String {
+        let [r, g, b, a] = self.to_rgba8();
+        format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+    }
+
+    pub fn r(self) -> f32 { self.r }
+    pub fn g(self) -> f32 { self.g }
+    pub fn b(self) -> f32 { self.b }
+    pub fn a(self) -> f32 { self.a }
+
+    /// Same color with a different alpha.
+    pub fn with_alpha(self, a: f32) -> Self {
+        Self::new(self.r, self.g, self.b, a)
+    }
+
+    /// Linearly interpolates between this color and another, where t = 0.0 is this color and t = 1.0 is the other.
+    /// Interpolates sRGB channels directly. Use [`Color::to_linear`] beforehand for physically linear mixing.
+    pub fn lerp(self, other: Color, t: f32) -> Self {
+        self + (other - self) * t
+    }
+
+    /// Increases the HSL lightness by an amount (0.0 - 1.0).
+    pub fn lighten(self, amount: f32) -> Self {
+        let [h, s, l, a] = self.to_hsla();
+        Self::hsla(h, s, (l + amount).clamp(0.0, 1.0), a)
+    }
+
+    /// Decreases the HSL lightness by an amount (0.0 - 1.0).
+    pub fn darken(self, amount: f32) -> Self {
+        self.lighten(-amount)
+    }
+
+    /// Converts sRGB channels to linear channels.
+    /// Alpha is left unchanged.
+    pub fn to_linear(self) -> Self {
+        Self::new(srgb_to_linear(self.r), srgb_to_linear(self.g), srgb_to_linear(self.b), self.a)
+    }
+
+    /// Converts linear channels to sRGB channels, undoing [`Color::to_linear`].
+    /// Alpha is left unchanged.
+    pub fn to_srgb(self) -> Self {
+        Self::new(linear_to_srgb(self.r), linear_to_srgb(self.g), linear_to_srgb(self.b), self.a)
+    }
+
+    /// Creates an opaque color from a hue in degrees, and a saturation and lightness (0.0 - 1.0).
+    pub fn hsl(h: f32, s: f32, l: f32) -> Self {
+        Self::hsla(h, s, l, 1.0)
+    }
+
+    /// Creates a color from a hue in degrees, and a saturation, lightness and alpha (0.0 - 1.0).
+    pub fn hsla(h: f32, s: f32, l: f32, a: f32) -> Self {
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let [r, g, b] = hue_to_rgb(h, chroma, l - chroma / 2.0);
+        Self::new(r, g, b, a)
+    }
+
+    /// Converts to hue in degrees, saturation, lightness and alpha.
+    pub fn to_hsla(self) -> [f32; 4] {
+        let (max, min) = (self.max_channel(), self.min_channel());
+        let chroma = max - min;
+        let l = (max + min) / 2.0;
+        let s = if l <= 0.0 || l >= 1.0 { 0.0 } else { chroma / (1.0 - (2.0 * l - 1.0).abs()) };
+        [self.hue(max, chroma), s, l, self.a]
+    }
+
+    /// Creates an opaque color from a hue in degrees, and a saturation and value (0.0 - 1.0).
+    pub fn hsv(h: f32, s: f32, v: f32) -> Self {
+        Self::hsva(h, s, v, 1.0)
+    }
+
+    /// Creates a color from a hue in degrees, and a saturation, value and alpha (0.0 - 1.0).
+    pub fn hsva(h: f32, s: f32, v: f32, a: f32) -> Self {
+        let chroma = v * s;
+        let [r, g, b] = hue_to_rgb(h, chroma, v - chroma);
+        Self::new(r, g, b, a)
+    }
+
+    /// Converts to hue in degrees, saturation, value and alpha.
+    pub fn to_hsva(self) -> [f32; 4] {
+        let (max, min) = (self.max_channel(), self.min_channel());
+        let chroma = max - min;
+        let s = if max <= 0.0 { 0.0 } else { chroma / max };
+        [self.hue(max, chroma), s, max, self.a]
+    }
+
+    /// Creates an opaque color from OKLCH lightness (0.0 - 1.0), chroma and hue in degrees.
+    pub fn oklch(l: f32, c: f32, h: f32) -> Self {
+        Self::oklcha(l, c, h, 1.0)
+    }
+
+    /// Creates a color from OKLCH lightness (0.0 - 1.0), chroma, hue in degrees and alpha.
+    pub fn oklcha(l: f32, c: f32, h: f32, alpha: f32) -> Self {
+        let (sin, cos) = h.to_radians().sin_cos();
+        let (a, b) = (c * cos, c * sin);
+        let l_ = (l + 0.396_337_78 * a + 0.215_803_76 * b).powi(3);
+        let m_ = (l - 0.105_561_346 * a - 0.063_854_17 * b).powi(3);
+        let s_ = (l - 0.089_484_18 * a - 1.291_485_5 * b).powi(3);
+        let linear = Self::new(
+            4.076_741_7 * l_ - 3.307_711_6 * m_ + 0.230_969_94 * s_,
+            -1.268_438 * l_ + 2.609_757_4 * m_ - 0.341_319_38 * s_,
+            -0.004_196_086_3 * l_ - 0.703_418_6 * m_ + 1.707_614_7 * s_,
+            alpha
+        );
+        linear.to_srgb()
+    }
+
+    /// Converts to OKLCH lightness, chroma, hue in degrees and alpha.
+    pub fn to_oklcha(self) -> [f32; 4] {
+        let Self { r, g, b, a: alpha } = self.to_linear();
+        let l_ = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+        let m_ = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+        let s_ = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+        let l = 0.210_454_26 * l_ + 0.793_617_8 * m_ - 0.004_072_047 * s_;
+        let a = 1.977_998_5 * l_ - 2.428_592_2 * m_ + 0.450_593_7 * s_;
+        let b = 0.025_904_037 * l_ + 0.782_771_77 * m_ - 0.808_675_77 * s_;
+        let c = (a * a + b * b).sqrt();
+        let h = if c < 1e-4 { 0.0 } else { b.atan2(a).to_degrees().rem_euclid(360.0) };
+        [l, c, h, alpha]
+    }
+
+    fn max_channel(self) -> f32 { self.r.max(self.g).max(self.b) }
+    fn min_channel(self) -> f32 { self.r.min(self.g).min(self.b) }
+
+    // Hue in degrees shared by HSL and HSV.
+    fn hue(self, max: f32, chroma: f32) -> f32 {
+        if chroma <= 0.0 {
+            return 0.0;
+        }
+        let hue = if max == self.r {
+            ((self.g - self.b) / chroma).rem_euclid(6.0)
+        }
+        else if max == self.g {
+            (self.b - self.r) / chroma + 2.0
+        }
+        else {
+            (self.r - self.g) / chroma + 4.0
+        };
+        hue * 60.0
+    }
+}
+
+impl Default for Color {
+    fn default() -> Self {
+        Color::WHITE
+    }
+}
+impl From<[f32; 4]> for Color {
+    fn from(value: [f32; 4]) -> Self {
+        Self::new(value[0], value[1], value[2], value[3])
+    }
+}
+impl From<[f32; 3]> for Color {
+    fn from(value: [f32; 3]) -> Self {
+        Self::new(value[0], value[1], value[2], 1.0)
+    }
+}
+
+impl From<Color> for [f32; 4] {
+    fn from(value: Color) -> Self {
+        [value.r, value.g, value.b, value.a]
+    }
+}
+impl From<Color> for [f32; 3] {
+    fn from(value: Color) -> Self {
+        [value.r, value.g, value.b]
+    }
+}
+impl From<Color> for wgpu::Color {
+    fn from(value: Color) -> Self {
+        Self { r: value.r as f64, g: value.g as f64, b: value.b as f64, a: value.a as f64 }
+    }
+}
+
+impl Add for Color {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.r + rhs.r, self.g + rhs.g, self.b + rhs.b, self.a + rhs.a)
+    }
+}
+
+impl Sub for Color {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.r - rhs.r, self.g - rhs.g, self.b - rhs.b, self.a - rhs.a)
+    }
+}
+
+impl Mul for Color {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new(self.r * rhs.r, self.g * rhs.g, self.b * rhs.b, self.a * rhs.a)
+    }
+}
+
+impl Div for Color {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self::Output {
+        Self::new(self.r / rhs.r, self.g / rhs.g, self.b / rhs.b, self.a / rhs.a)
+    }
+}
+
+impl Mul<f32> for Color {
+    type Output = Self;
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self::new(self.r * rhs, self.g * rhs, self.b * rhs, self.a * rhs)
+    }
+}
+
+impl Div<f32> for Color {
+    type Output = Self;
+    fn div(self, rhs: f32) -> Self::Output {
+        Self::new(self.r / rhs, self.g / rhs, self.b / rhs, self.a / rhs)
+    }
+}
+
+impl Mul<Color> for f32 {
+    type Output = Color;
+    fn mul(self, rhs: Color) -> Self::Output {
+        Self::Output::new(self * rhs.r, self * rhs.g, self * rhs.b, self * rhs.a)
+    }
+}
+
+impl Div<Color> for f32 {
+    type Output = Color;
+    fn div(self, rhs: Color) -> Self::Output {
+        Self::Output::new(self / rhs.r, self / rhs.g, self / rhs.b, self / rhs.a)
+    }
+}
+
+// Converts a hue in degrees and chroma to rgb, offset by an amount to match lightness or value.
+fn hue_to_rgb(h: f32, chroma: f32, offset: f32) -> [f32; 3] {
+    let h = h.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+    let [r, g, b] = match h as u32 {
+        0 => [chroma, x, 0.0],
+        1 => [x, chroma, 0.0],
+        2 => [0.0, chroma, x],
+        3 => [0.0, x, chroma],
+        4 => [x, 0.0, chroma],
+        _ => [chroma, 0.0, x]
+    };
+    [r + offset, g + offset, b + offset]
+}
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+}
+
+fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.003_130_8 { c * 12.92 } else { 1.055 * c.powf(1.0 / 2.4) - 0.055 }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use crate::Color;
+
+    pub(crate) fn assert_close(expected: Color, actual: Color) {
+        let diff = expected - actual;
+        let max = diff.r().abs().max(diff.g().abs()).max(diff.b().abs()).max(diff.a().abs());
+        assert!(max < 0.002, "expected {expected:?}, got {actual:?}");
+    }
+
+    #[test]
+    fn test_premultiply() {
+        let color = Color::new(1.0, 0.5, 0.25, 0.5);
+        assert_eq!(Color::new(0.5, 0.25, 0.125, 0.5), color.premultiply());
+        assert_close(color, color.premultiply().unpremultiply());
+        assert_eq!(Color::WHITE, Color::WHITE.premultiply().unpremultiply());
+
+        // Fully transparent colors lose their channels.
+        let clear = Color::new(1.0, 0.5, 0.25, 0.0);
+        assert_eq!(Color::new(0.0, 0.0, 0.0, 0.0), clear.premultiply());
+        assert_eq!(Color::new(0.0, 0.0, 0.0, 0.0), clear.premultiply().unpremultiply());
+        assert_eq!(Color::new(0.0, 0.0, 0.0, 0.0), clear.unpremultiply());
+    }
+}
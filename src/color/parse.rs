@@ -0,0 +1,337 @@
+use std::str::FromStr;
+
+use crate::{Color, GewyError, Result};
+
+impl Color {
+
+    /// Parses a color from a string.
+    /// Supports hex ("#rgb", "#rgba", "#rrggbb", "#rrggbbaa"), "rgb()", "rgba()", "hsl()", "hsla()", "oklch()" and CSS named colors.
+    /// Function arguments may be separated by commas or spaces, with an optional "/" before alpha.
+    pub fn parse(source: &str) -> Result<Self> {
+        let source = source.trim().to_ascii_lowercase();
+        let invalid = || GewyError::InvalidColor(source.clone());
+        if let Some(hex) = source.strip_prefix('#') {
+            return parse_hex(hex).ok_or_else(invalid);
+        }
+        if let Some((name, args)) = source.split_once('(') {
+            let args = args.strip_suffix(')').ok_or_else(invalid)?;
+            return parse_function(name.trim(), args).ok_or_else(invalid);
+        }
+        named(&source).ok_or_else(invalid)
+    }
+}
+
+impl FromStr for Color {
+    type Err = GewyError;
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) { return None }
+    let digit = |i: usize| u8::from_str_radix(&hex[i..i+1], 16).ok().map(|d| d * 17);
+    let byte = |i: usize| u8::from_str_radix(&hex[i..i+2], 16).ok();
+    let [r, g, b, a] = match hex.len() {
+        3 => [digit(0)?, digit(1)?, digit(2)?, 255],
+        4 => [digit(0)?, digit(1)?, digit(2)?, digit(3)?],
+        6 => [byte(0)?, byte(2)?, byte(4)?, 255],
+        8 => [byte(0)?, byte(2)?, byte(4)?, byte(6)?],
+        _ => return None
+    };
+    Some(Color::from_rgba8(r, g, b, a))
+}
+
+fn parse_function(name: &str, args: &str) -> Option<Color> {
+
+    // Splits arguments, where alpha may follow a "/".
+    let (args, alpha) = match args.split_once('/') {
+        Some((args, alpha)) => (args, Some(alpha.trim())),
+        None => (args, None)
+    };
+    let mut args: Vec<&str> = args
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|arg| !arg.is_empty())
+        .collect();
+    if let Some(alpha) = alpha {
+        args.push(alpha);
+    }
+    let alpha = match args.len() {
+        3 => 1.0,
+        4 => parse_unit(args[3], 1.0)?,
+        _ => return None
+    };
+
+    // Parses the remaining arguments based on the function.
+    match name {
+        "rgb" | "rgba" => Some(Color::new(
+            parse_unit(args[0], 255.0)?,
+            parse_unit(args[1], 255.0)?,
+            parse_unit(args[2], 255.0)?,
+            alpha
+        )),
+        "hsl" | "hsla" => Some(Color::hsla(
+            parse_hue(args[0])?,
+            parse_unit(args[1], 100.0)?,
+            parse_unit(args[2], 100.0)?,
+            alpha
+        )),
+        "oklch" => Some(Color::oklcha(
+            parse_unit(args[0], 1.0)?,
+            parse_number(args[1], 0.4)?,
+            parse_hue(args[2])?,
+            alpha
+        )),
+        _ => None
+    }
+}
+
+// Parses a number or percentage into the range 0.0 - 1.0, where max is the number that maps to 1.0.
+fn parse_unit(arg: &str, max: f32) -> Option<f32> {
+    let value = match arg.strip_suffix('%') {
+        Some(percent) => parse_finite(percent)? / 100.0,
+        None => parse_finite(arg)? / max
+    };
+    Some(value.clamp(0.0, 1.0))
+}
+
+// Parses a number, where a percentage is relative to the number specified.
+fn parse_number(arg: &str, percent_of: f32) -> Option<f32> {
+    match arg.strip_suffix('%') {
+        Some(percent) => Some(parse_finite(percent)? / 100.0 * percent_of),
+        None => parse_finite(arg)
+    }
+}
+
+// Parses an angle in degrees, with an optional "deg" or "turn" suffix.
+fn parse_hue(arg: &str) -> Option<f32> {
+    if let Some(turns) = arg.strip_suffix("turn") {
+        return Some(parse_finite(turns)? * 360.0);
+    }
+    parse_finite(arg.strip_suffix("deg").unwrap_or(arg))
+}
+
+// Parses a number, rejecting NaN and infinities.
+fn parse_finite(arg: &str) -> Option<f32> {
+    arg.parse::<f32>().ok().filter(|value| value.is_finite())
+}
+
+fn named(name: &str) -> Option<Color> {
+    if name == "transparent" {
+        return Some(Color::new(0.0, 0.0, 0.0, 0.0));
+    }
+    let index = NAMED_COLORS.binary_search_by(|(n, _)| n.cmp(&name)).ok()?;
+    let rgb = NAMED_COLORS[index].1;
+    Some(Color::from_rgba8((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8, 255))
+}
+
+// CSS named colors, sorted by name.
+const NAMED_COLORS: [(&str, u32); 148] = [
+    ("aliceblue", 0xf0f8ff),
+    ("antiquewhite", 0xfaebd7),
+    ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff),
+    ("beige", 0xf5f5dc),
+    ("bisque", 0xffe4c4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xffebcd),
+    ("blue", 0x0000ff),
+    ("blueviolet", 0x8a2be2),
+    ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887),
+    ("cadetblue", 0x5f9ea0),
+    ("chartreuse", 0x7fff00),
+    ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50),
+    ("cornflowerblue", 0x6495ed),
+    ("cornsilk", 0xfff8dc),
+    ("crimson", 0xdc143c),
+    ("cyan", 0x00ffff),
+    ("darkblue", 0x00008b),
+    ("darkcyan", 0x008b8b),
+    ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xa9a9a9),
+    ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b),
+    ("darkolivegreen", 0x556b2f),
+    ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc),
+    ("darkred", 0x8b0000),
+    ("darksalmon", 0xe9967a),
+    ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b),
+    ("darkslategray", 0x2f4f4f),
+    ("darkslategrey", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1),
+    ("darkviolet", 0x9400d3),
+    ("deeppink", 0xff1493),
+    ("deepskyblue", 0x00bfff),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1e90ff),
+    ("firebrick", 0xb22222),
+    ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22),
+    ("fuchsia", 0xff00ff),
+    ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff),
+    ("gold", 0xffd700),
+    ("goldenrod", 0xdaa520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xadff2f),
+    ("grey", 0x808080),
+    ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4),
+    ("indianred", 0xcd5c5c),
+    ("indigo", 0x4b0082),
+    ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c),
+    ("lavender", 0xe6e6fa),
+    ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00),
+    ("lemonchiffon", 0xfffacd),
+    ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080),
+    ("lightcyan", 0xe0ffff),
+    ("lightgoldenrodyellow", 0xfafad2),
+    ("lightgray", 0xd3d3d3),
+    ("lightgreen", 0x90ee90),
+    ("lightgrey", 0xd3d3d3),
+    ("lightpink", 0xffb6c1),
+    ("lightsalmon", 0xffa07a),
+    ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de),
+    ("lightyellow", 0xffffe0),
+    ("lime", 0x00ff00),
+    ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6),
+    ("magenta", 0xff00ff),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd),
+    ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db),
+    ("mediumseagreen", 0x3cb371),
+    ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a),
+    ("mediumturquoise", 0x48d1cc),
+    ("mediumvioletred", 0xc71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xf5fffa),
+    ("mistyrose", 0xffe4e1),
+    ("moccasin", 0xffe4b5),
+    ("navajowhite", 0xffdead),
+    ("navy", 0x000080),
+    ("oldlace", 0xfdf5e6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500),
+    ("orangered", 0xff4500),
+    ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa),
+    ("palegreen", 0x98fb98),
+    ("paleturquoise", 0xafeeee),
+    ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5),
+    ("peachpuff", 0xffdab9),
+    ("peru", 0xcd853f),
+    ("pink", 0xffc0cb),
+    ("plum", 0xdda0dd),
+    ("powderblue", 0xb0e0e6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f),
+    ("royalblue", 0x4169e1),
+    ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072),
+    ("sandybrown", 0xf4a460),
+    ("seagreen", 0x2e8b57),
+    ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d),
+    ("silver", 0xc0c0c0),
+    ("skyblue", 0x87ceeb),
+    ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xfffafa),
+    ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4),
+    ("tan", 0xd2b48c),
+    ("teal", 0x008080),
+    ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347),
+    ("turquoise", 0x40e0d0),
+    ("violet", 0xee82ee),
+    ("wheat", 0xf5deb3),
+    ("white", 0xffffff),
+    ("whitesmoke", 0xf5f5f5),
+    ("yellow", 0xffff00),
+    ("yellowgreen", 0x9acd32)
+];
+
+#[cfg(test)]
+mod test {
+    use crate::Color;
+    use crate::color::test::assert_close;
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(Color::from_rgba8(0x11, 0x22, 0x33, 0xff), Color::parse("#123").unwrap());
+        assert_eq!(Color::from_rgba8(0x11, 0x22, 0x33, 0x44), Color::parse("#1234").unwrap());
+        assert_eq!(Color::from_rgba8(0x12, 0x34, 0x56, 0xff), Color::parse("#123456").unwrap());
+        assert_eq!(Color::from_rgba8(0x12, 0x34, 0x56, 0x78), Color::parse(" #12345678 ").unwrap());
+        assert_eq!("#12345678", Color::parse("#12345678").unwrap().to_hex());
+        assert!(Color::parse("#12345").is_err());
+        assert!(Color::parse("#gggggg").is_err());
+        assert!(Color::parse("#+1+2+3").is_err());
+        assert!(Color::parse("#+1+2+3+4").is_err());
+    }
+
+    #[test]
+    fn test_parse_functions() {
+        let expected = Color::from_rgba8(255, 128, 0, 255);
+        assert_eq!(expected, Color::parse("rgb(255, 128, 0)").unwrap());
+        assert_eq!(expected, Color::parse("rgb(255 128 0)").unwrap());
+        assert_eq!(expected.with_alpha(0.5), Color::parse("rgba(255, 128, 0, 0.5)").unwrap());
+        assert_eq!(expected.with_alpha(0.5), Color::parse("rgb(255 128 0 / 50%)").unwrap());
+        assert_close(Color::RED, Color::parse("hsl(0, 100%, 50%)").unwrap());
+        assert_close(Color::BLUE.with_alpha(0.25), Color::parse("hsla(240deg 100% 50% / 0.25)").unwrap());
+        assert_close(Color::WHITE, Color::parse("oklch(100% 0 0)").unwrap());
+        assert!(Color::parse("rgb(1, 2)").is_err());
+        assert!(Color::parse("cmyk(1, 2, 3, 4)").is_err());
+        assert!(Color::parse("rgb(nan, 0, 0)").is_err());
+        assert!(Color::parse("rgb(0, inf%, 0)").is_err());
+        assert!(Color::parse("hsl(infinity, 50%, 50%)").is_err());
+        assert!(Color::parse("oklch(0.5, NaN, 120)").is_err());
+    }
+
+    #[test]
+    fn test_parse_named() {
+        assert_eq!(Color::from_rgba8(0x66, 0x33, 0x99, 0xff), Color::parse("RebeccaPurple").unwrap());
+        assert_eq!(0.0, Color::parse("transparent").unwrap().a());
+        assert!(Color::parse("notacolor").is_err());
+    }
+
+    #[test]
+    fn test_conversions() {
+        let color = Color::from_rgba8(0x3a, 0x7b, 0xd5, 0xff);
+        let [h, s, l, a] = color.to_hsla();
+        assert_close(color, Color::hsla(h, s, l, a));
+        let [h, s, v, a] = color.to_hsva();
+        assert_close(color, Color::hsva(h, s, v, a));
+        let [l, c, h, a] = color.to_oklcha();
+        assert_close(color, Color::oklcha(l, c, h, a));
+        assert_close(color, color.to_linear().to_srgb());
+        assert_close(Color::GRAY, Color::BLACK.lerp(Color::WHITE, 0.5));
+        assert_close(Color::WHITE, Color::GRAY.lighten(0.5));
+        assert_close(Color::BLACK, Color::GRAY.darken(0.5));
+    }
+}
@@ -0,0 +1,69 @@
+use std::any::Any;
+use std::fmt;
+use std::rc::Rc;
+use crate::{Color, MouseButton, NodeId, Vec2};
+
+/// Data carried by a drag, of any type chosen by the widget that started it.
+/// Clones share the same data.
+#[derive(Clone)]
+pub struct DragPayload(Rc<dyn Any>);
+
+impl DragPayload {
+    pub fn new(data: impl Any) -> Self {
+        Self(Rc::new(data))
+    }
+
+    /// Tries to downcast the data to the type specified.
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.0.downcast_ref()
+    }
+
+    /// True if the data is the type specified.
+    pub fn is<T: Any>(&self) -> bool {
+        self.0.is::<T>()
+    }
+}
+
+impl fmt::Debug for DragPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DragPayload(..)")
+    }
+}
+
+/// Rectangle painted under the cursor while dragging, over every node.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct DragPreview {
+    pub size: Vec2,
+    pub color: Color,
+    pub corner_radius: f32,
+    /// Offset of the top left of the preview from the cursor.
+    pub offset: Vec2
+}
+
+impl DragPreview {
+    pub fn new(size: Vec2, color: Color) -> Self {
+        Self { size, color, corner_radius: 0.0, offset: Vec2::ZERO }
+    }
+
+    pub fn with_corner_radius(mut self, corner_radius: f32) -> Self {
+        self.corner_radius = corner_radius;
+        self
+    }
+
+    pub fn with_offset(mut self, offset: Vec2) -> Self {
+        self.offset = offset;
+        self
+    }
+}
+
+// Drag in progress, ended by releasing the button it started with.
+#[derive(Clone, Debug)]
+pub(crate) struct Drag {
+    pub source_id: NodeId,
+    pub button: MouseButton,
+    pub payload: DragPayload,
+    pub preview: Option<DragPreview>,
+    // Node under the cursor, and whether it or one of its ancestors accepted the drop the last time the drag entered or moved over it.
+    pub target_id: Option<NodeId>,
+    pub accepted: bool
+}
@@ -1,7 +1,14 @@
-use crate::{Style, Pane, Descendants, NodeId, Node, RadioButton};
+use std::marker::PhantomData;
+use crate::{Style, Pane, Descendants, NodeId, Node, RadioButton, Text, RichText, Paragraph, TextField, Event, EventControl};
 
 pub trait Class {
     fn apply(self, style: &mut Style);
+
+    /// Applies to the style of a node.
+    /// Overridden by classes that also affect the node itself, like [`crate::StyleSheet`] class names.
+    fn apply_to_node(self, node: &mut Node) where Self: Sized {
+        self.apply(&mut node.style);
+    }
 }
 
 impl<F> Class for F
@@ -17,6 +24,36 @@ impl Class for () {
     fn apply(self, _style: &mut Style) {}
 }
 
+/// Name of a [`crate::StyleSheet`] class, whose rules get applied when the node is inserted.
+impl Class for &str {
+    fn apply(self, _style: &mut Style) {}
+    fn apply_to_node(self, node: &mut Node) {
+        node.class_names.push(self.to_owned());
+    }
+}
+
+/// Closure that handles events of type E on a node, created with [`on`].
+pub struct On<E, F> {
+    handler: F,
+    event: PhantomData<fn(&E)>
+}
+
+/// Attaches a closure to the node that handles events of type E, like a class.
+pub fn on<E: Event, F: FnMut(&E, &mut EventControl) + 'static>(handler: F) -> On<E, F> {
+    On { handler, event: PhantomData }
+}
+
+impl<E, F> Class for On<E, F>
+where
+    E: Event,
+    F: FnMut(&E, &mut EventControl) + 'static
+{
+    fn apply(self, _style: &mut Style) {}
+    fn apply_to_node(self, node: &mut Node) {
+        node.handlers.push(crate::handler(self.handler));
+    }
+}
+
 impl<C1> Class for (C1,)
 where
     C1: Class
@@ -24,6 +61,9 @@ where
     fn apply(self, style: &mut Style) {
         self.0.apply(style);
     }
+    fn apply_to_node(self, node: &mut Node) {
+        self.0.apply_to_node(node);
+    }
 }
 
 impl<C1, C2> Class for (C1,C2)
@@ -35,6 +75,10 @@ where
         self.0.apply(style);
         self.1.apply(style);
     }
+    fn apply_to_node(self, node: &mut Node) {
+        self.0.apply_to_node(node);
+        self.1.apply_to_node(node);
+    }
 }
 
 impl<C1, C2, C3> Class for (C1,C2,C3)
@@ -48,6 +92,11 @@ where
         self.1.apply(style);
         self.2.apply(style);
     }
+    fn apply_to_node(self, node: &mut Node) {
+        self.0.apply_to_node(node);
+        self.1.apply_to_node(node);
+        self.2.apply_to_node(node);
+    }
 }
 
 impl<C1, C2, C3, C4> Class for (C1,C2,C3,C4)
@@ -63,11 +112,17 @@ where
         self.2.apply(style);
         self.3.apply(style);
     }
+    fn apply_to_node(self, node: &mut Node) {
+        self.0.apply_to_node(node);
+        self.1.apply_to_node(node);
+        self.2.apply_to_node(node);
+        self.3.apply_to_node(node);
+    }
 }
 
 pub fn pane(class: impl Class, descendants: &mut Descendants, descendants_fn: impl FnOnce(&mut Descendants)) -> NodeId {
     let mut node = Node::from_widget(Pane);
-    class.apply(&mut node.style);
+    class.apply_to_node(&mut node);
     let mut descendants = descendants.insert(node);
     descendants_fn(&mut descendants);
     descendants.node_id()
@@ -75,13 +130,31 @@ pub fn pane(class: impl Class, descendants: &mut Descendants, descendants_fn: im
 
 pub fn rect(class: impl Class, descendants: &mut Descendants) -> NodeId {
     let mut node = Node::from_widget(Pane);
-    class.apply(&mut node.style);
+    class.apply_to_node(&mut node);
     let descendants = descendants.insert(node);
     descendants.node_id()
 }
 
 pub fn radio_button(class: impl Class, descendants: &mut Descendants) -> NodeId {
     let mut node = Node::from_widget(RadioButton::default());
-    class.apply(&mut node.style);
+    class.apply_to_node(&mut node);
+    descendants.insert(node).node_id()
+}
+
+pub fn text(class: impl Class, text: Text, descendants: &mut Descendants) -> NodeId {
+    let mut node = Node::from_widget(text);
+    class.apply_to_node(&mut node);
     descendants.insert(node).node_id()
-}
\ No newline at end of file
+}
+
+pub fn rich_text(class: impl Class, paragraph: Paragraph, descendants: &mut Descendants) -> NodeId {
+    let mut node = Node::from_widget(RichText::new(paragraph));
+    class.apply_to_node(&mut node);
+    descendants.insert(node).node_id()
+}
+
+pub fn text_field(class: impl Class, text_field: TextField, descendants: &mut Descendants) -> NodeId {
+    let mut node = Node::from_widget(text_field);
+    class.apply_to_node(&mut node);
+    descendants.insert(node).node_id()
+}
@@ -7,5 +7,15 @@ pub enum GewyError {
     #[display(fmt = "Node not found")]
     NodeNotFound,
     #[display(fmt = "Parent not found")]
-    ParentNodeNotFound
+    ParentNodeNotFound,
+    #[display(fmt = "Too many events fired while processing events")]
+    EventLimit,
+    #[display(fmt = "Invalid color: {}", _0)]
+    InvalidColor(#[error(not(source))] String),
+    #[display(fmt = "Invalid style sheet at line {}, column {}: {}", line, column, message)]
+    InvalidStyleSheet { line: usize, column: usize, message: String },
+    #[display(fmt = "Invalid font")]
+    InvalidFont,
+    #[display(fmt = "IO error: {}", _0)]
+    Io(#[error(not(source))] String)
 }
\ No newline at end of file
@@ -1,6 +1,7 @@
 use std::any::Any;
+use std::time::Duration;
 
-use crate::{NodeId, Name, CursorIcon};
+use crate::{NodeId, Name, CursorIcon, Key, Modifiers, MouseButton, ScrollDelta, Vec2, TimerId, TimerRequest, DragPayload, DragPreview};
 
 /// Any event type.
 pub trait Event: Any + 'static {}
@@ -25,6 +26,17 @@ impl<E: Event> From<E> for DynEvent {
     }
 }
 
+/// Closure attached to a [`crate::Node`] that handles events of one type.
+pub(crate) type Handler = Box<dyn FnMut(&mut EventControl)>;
+
+pub(crate) fn handler<E: Event>(mut handler: impl FnMut(&E, &mut EventControl) + 'static) -> Handler {
+    Box::new(move |ctl| {
+        if let Some(event) = ctl.as_event::<E>() {
+            handler(event, ctl);
+        }
+    })
+}
+
 /// Information about a node where an event originated.
 #[derive(Copy, Clone, Eq, PartialEq, Default, Debug)]
 pub struct NodeOrigin {
@@ -32,6 +44,18 @@ pub struct NodeOrigin {
     pub name: Option<Name>
 }
 
+/// Phase of the dispatch of an event fired on a node.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Default, Debug)]
+pub enum EventPhase {
+    /// Ancestors of the target node see the event with [`crate::Widget::capture_event`], from the root down.
+    Capture,
+    /// The target node sees the event.
+    #[default]
+    Target,
+    /// Ancestors of the target node see the event, from the target up to the root.
+    Bubble
+}
+
 /// Represents an event "control flow".
 /// Provides an event that occured, on what node it occurred (if any),
 /// and allows for the user to stop the propogation of the event if they desire.
@@ -39,11 +63,19 @@ pub struct EventControl<'e> {
     /// Event that was fired.
     pub event: &'e DynEvent,
     pub(crate) origin: Option<NodeOrigin>,
+    pub(crate) phase: EventPhase,
     pub(crate) outgoing_events: Vec<DynEvent>,
     pub(crate) cursor_icon: Option<CursorIcon>,
     pub(crate) stop: bool,
     pub(crate) repaint: bool,
-    pub(crate) pressed: bool
+    pub(crate) pressed: bool,
+    pub(crate) captured: bool,
+    pub(crate) focused: bool,
+    pub(crate) ticks: Option<bool>,
+    pub(crate) timers: Vec<TimerRequest>,
+    pub(crate) drag: Option<(DragPayload, Option<DragPreview>)>,
+    pub(crate) accepted: bool,
+    pub(crate) cursor_position: Vec2
 }
 
 impl<'e> EventControl<'e> {
@@ -52,26 +84,40 @@ impl<'e> EventControl<'e> {
         Self {
             event,
             origin,
+            phase: EventPhase::Target,
             outgoing_events: Vec::new(),
             cursor_icon: None,
             stop: false,
             repaint: false,
-            pressed: false
+            pressed: false,
+            captured: false,
+            focused: false,
+            ticks: None,
+            timers: Vec::new(),
+            drag: None,
+            accepted: false,
+            cursor_position: Vec2::ZERO
         }
     }
 
     /// Info about the descendant node that the event came from.
+    /// In the [`EventPhase::Capture`] phase, this is the target node the event is headed to.
     pub fn origin(&self) -> Option<NodeOrigin> {
         self.origin
     }
 
+    /// Phase of the dispatch the event is in.
+    pub fn phase(&self) -> EventPhase {
+        self.phase
+    }
+
     /// True if the event is the type specified.
     pub fn is_event<E: Event>(&self) -> bool {
         self.event.is_event::<E>()
     }
 
     /// Tries to downcast the event to the type specified.
-    pub fn as_event<E: Event>(&self) -> Option<&E> {
+    pub fn as_event<E: Event>(&self) -> Option<&'e E> {
         self.event.as_event::<E>()
     }
 
@@ -88,11 +134,13 @@ impl<'e> EventControl<'e> {
     }
 
     /// Fires an outgoing event for ancestors to react to.
+    /// It is queued, and fired after the current event is done with.
     pub fn fire(&mut self, event: impl Into<DynEvent>) {
         self.outgoing_events.push(event.into());
     }
 
     /// If invoked for a bubble [`Event`], will stop the event propagation to further ancestors.
+    /// In the [`EventPhase::Capture`] phase, the event also never reaches its target.
     pub fn stop(&mut self) {
         self.stop = true;
     }
@@ -103,10 +151,68 @@ impl<'e> EventControl<'e> {
     }
 
     /// Marks the [`crate::Node`] of the [`crate::Widget`] as "pressed".
+    /// It receives a [`ReleaseEvent`] if the button is released over it.
+    /// Outside of a [`PressEvent`], the node is pressed with the left button.
     pub fn press(&mut self) {
         self.pressed = true;
     }
 
+    /// Presses the [`crate::Node`] of the [`crate::Widget`] and captures the pointer, like when dragging.
+    /// Until the button is released, the node receives every [`PointerMoveEvent`],
+    /// and then the [`ReleaseEvent`] wherever the cursor is.
+    pub fn capture(&mut self) {
+        self.captured = true;
+    }
+
+    /// Gives the [`crate::Node`] of the [`crate::Widget`] keyboard focus, so that it receives key and character events.
+    pub fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    /// Subscribes the [`crate::Node`] of the [`crate::Widget`] to a [`TickEvent`] on every [`crate::Gewy::tick`], like for animations.
+    pub fn subscribe_ticks(&mut self) {
+        self.ticks = Some(true);
+    }
+
+    /// Stops the [`TickEvent`]s that [`EventControl::subscribe_ticks`] started.
+    pub fn unsubscribe_ticks(&mut self) {
+        self.ticks = Some(false);
+    }
+
+    /// Fires a [`TimerEvent`] with the id on the [`crate::Node`] of the [`crate::Widget`] once the delay has passed.
+    /// Replaces the node's timer with the same id, if any.
+    pub fn start_timer(&mut self, id: TimerId, delay: Duration) {
+        self.timers.push(TimerRequest::Start { id, delay, repeat: false });
+    }
+
+    /// Like [`EventControl::start_timer`], but fires again every interval until stopped.
+    pub fn start_repeating_timer(&mut self, id: TimerId, interval: Duration) {
+        self.timers.push(TimerRequest::Start { id, delay: interval, repeat: true });
+    }
+
+    /// Stops the node's timer with the id, if any, before it fires again.
+    pub fn stop_timer(&mut self, id: TimerId) {
+        self.timers.push(TimerRequest::Stop(id));
+    }
+
+    /// Starts dragging the payload from the [`crate::Node`] of the [`crate::Widget`], until the button held is released.
+    /// Usually invoked on a [`PointerMoveEvent`] after the node was pressed.
+    /// The node receives a [`DragEndEvent`] when the drag ends.
+    pub fn start_drag(&mut self, payload: impl Any, preview: Option<DragPreview>) {
+        self.drag = Some((DragPayload::new(payload), preview));
+    }
+
+    /// Accepts the drag in a [`DragEnterEvent`] or [`DragOverEvent`], so that releasing it over the node under the cursor drops it.
+    pub fn accept_drop(&mut self) {
+        self.accepted = true;
+    }
+
+    /// Position of the cursor relative to the top left of the [`crate::Node`] handling the event, including its border.
+    /// Matches the coordinates of the [`crate::Canvas`] the node is painted in.
+    pub fn cursor_position(&self) -> Vec2 {
+        self.cursor_position
+    }
+
     /// Sets the next desired cursor icon.
     pub fn set_cursor_icon(&mut self, cursor_icon: CursorIcon) {
         self.cursor_icon = Some(cursor_icon);
@@ -115,14 +221,66 @@ impl<'e> EventControl<'e> {
 
 
 // Built-in events
-#[derive(Copy, Clone, Eq, PartialEq, Default, Debug)]
-pub struct PressEvent;
+
+/// Fired on the node under the cursor when a mouse button is pressed.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct PressEvent {
+    pub button: MouseButton,
+    /// Position of the cursor.
+    pub position: Vec2,
+    /// Position of the cursor relative to the border region of the node the event was fired on.
+    pub local_position: Vec2,
+    pub modifiers: Modifiers,
+    /// Time set with [`crate::InputMapping::set_time`].
+    pub timestamp: Duration
+}
 impl Event for PressEvent {}
 
-#[derive(Copy, Clone, Eq, PartialEq, Default, Debug)]
-pub struct ReleaseEvent;
+/// Fired on a pressed node when the same button is released over it.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ReleaseEvent {
+    pub button: MouseButton,
+    /// Position of the cursor.
+    pub position: Vec2,
+    /// Position of the cursor relative to the border region of the node the event was fired on.
+    pub local_position: Vec2,
+    pub modifiers: Modifiers,
+    /// Time set with [`crate::InputMapping::set_time`].
+    pub timestamp: Duration
+}
 impl Event for ReleaseEvent {}
 
+/// Fired on the node under the cursor when a button is pressed and released over it without moving away.
+/// Not fired if the press was a [`LongPressEvent`].
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ClickEvent {
+    pub button: MouseButton,
+    /// Position of the cursor.
+    pub position: Vec2,
+    /// Position of the cursor relative to the border region of the node the event was fired on.
+    pub local_position: Vec2,
+    pub modifiers: Modifiers,
+    /// 1 for a single click, 2 for a double click, 3 for a triple click and so on.
+    pub count: u32,
+    /// Time set with [`crate::InputMapping::set_time`].
+    pub timestamp: Duration
+}
+impl Event for ClickEvent {}
+
+/// Fired on the pressed node when a button is held over it without moving away for [`crate::GestureConfig::long_press_time`].
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct LongPressEvent {
+    pub button: MouseButton,
+    /// Position of the cursor.
+    pub position: Vec2,
+    /// Position of the cursor relative to the border region of the node the event was fired on.
+    pub local_position: Vec2,
+    pub modifiers: Modifiers,
+    /// Time set with [`crate::InputMapping::set_time`].
+    pub timestamp: Duration
+}
+impl Event for LongPressEvent {}
+
 #[derive(Copy, Clone, Eq, PartialEq, Default, Debug)]
 pub struct GewyEnterEvent;
 impl Event for GewyEnterEvent {}
@@ -131,10 +289,14 @@ impl Event for GewyEnterEvent {}
 pub struct GewyExitEvent;
 impl Event for GewyExitEvent {}
 
+/// Fired on a node when the cursor moves over it or one of its children, having been outside of them.
+/// Does not bubble.
 #[derive(Copy, Clone, Eq, PartialEq, Default, Debug)]
 pub struct EnterEvent;
 impl Event for EnterEvent {}
 
+/// Fired on a node when the cursor leaves it and its children.
+/// Does not bubble.
 #[derive(Copy, Clone, Eq, PartialEq, Default, Debug)]
 pub struct ExitEvent;
 impl Event for ExitEvent {}
@@ -145,4 +307,253 @@ impl Event for FocusEvent {}
 
 #[derive(Copy, Clone, Eq, PartialEq, Default, Debug)]
 pub struct UnfocusEvent;
-impl Event for UnfocusEvent {}
\ No newline at end of file
+impl Event for UnfocusEvent {}
+
+/// Fired when the cursor moves, on the node that captured the pointer if any, or else the node under the cursor.
+#[derive(Copy, Clone, PartialEq, Default, Debug)]
+pub struct PointerMoveEvent {
+    /// Position of the cursor.
+    pub position: Vec2,
+    /// Position of the cursor relative to the border region of the node the event was fired on.
+    pub local_position: Vec2,
+    pub modifiers: Modifiers,
+    /// Time set with [`crate::InputMapping::set_time`].
+    pub timestamp: Duration
+}
+impl Event for PointerMoveEvent {}
+
+/// Fired on each node subscribed with [`EventControl::subscribe_ticks`] when the [`crate::Gewy`] ticks.
+/// Does not bubble.
+#[derive(Copy, Clone, Eq, PartialEq, Default, Debug)]
+pub struct TickEvent {
+    /// Time passed to [`crate::Gewy::tick`].
+    pub now: Duration,
+    /// Time since the previous tick, or zero for the first tick.
+    pub delta: Duration
+}
+impl Event for TickEvent {}
+
+/// Fired on the node that started a timer when it runs out.
+/// Does not bubble.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct TimerEvent {
+    pub id: TimerId,
+    /// Time passed to [`crate::Gewy::tick`], which may be later than when the timer ran out.
+    pub now: Duration
+}
+impl Event for TimerEvent {}
+
+/// Fired on the node under the cursor when a drag moves over it, having been over another node or none.
+#[derive(Clone, Debug)]
+pub struct DragEnterEvent {
+    pub payload: DragPayload,
+    /// Position of the cursor.
+    pub position: Vec2
+}
+impl Event for DragEnterEvent {}
+
+/// Fired on the node under the cursor whenever a drag moves, after any [`DragEnterEvent`].
+/// A widget accepts the drag with [`EventControl::accept_drop`], which lasts until the drag moves again.
+#[derive(Clone, Debug)]
+pub struct DragOverEvent {
+    pub payload: DragPayload,
+    /// Position of the cursor.
+    pub position: Vec2,
+    /// Position of the cursor relative to the border region of the node the event was fired on.
+    pub local_position: Vec2
+}
+impl Event for DragOverEvent {}
+
+/// Fired on the node that was under the cursor when a drag leaves it, or is cancelled.
+#[derive(Clone, Debug)]
+pub struct DragLeaveEvent {
+    pub payload: DragPayload
+}
+impl Event for DragLeaveEvent {}
+
+/// Fired on the node under the cursor when a drag that was accepted is released over it.
+#[derive(Clone, Debug)]
+pub struct DropEvent {
+    pub payload: DragPayload,
+    /// Position of the cursor.
+    pub position: Vec2,
+    /// Position of the cursor relative to the border region of the node the event was fired on.
+    pub local_position: Vec2
+}
+impl Event for DropEvent {}
+
+/// Fired on the node that started a drag when it ends.
+#[derive(Copy, Clone, Eq, PartialEq, Default, Debug)]
+pub struct DragEndEvent {
+    /// True if the drag was dropped on an accepting node, false if it was released elsewhere or cancelled.
+    pub dropped: bool
+}
+impl Event for DragEndEvent {}
+
+/// Fired on the focused node when a key is pressed, and repeatedly while it is held.
+/// Fired on the root node instead when no node is focused.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct KeyDownEvent {
+    pub key: Key,
+    pub modifiers: Modifiers
+}
+impl Event for KeyDownEvent {}
+
+/// Fired on the focused node when a key is released.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct KeyUpEvent {
+    pub key: Key,
+    pub modifiers: Modifiers
+}
+impl Event for KeyUpEvent {}
+
+/// Fired on the focused node when text is typed.
+#[derive(Clone, Eq, PartialEq, Default, Debug)]
+pub struct CharInputEvent {
+    pub text: String
+}
+impl Event for CharInputEvent {}
+
+/// Fired by editable widgets when the user changes their text.
+#[derive(Clone, Eq, PartialEq, Default, Debug)]
+pub struct TextChangedEvent {
+    pub text: String
+}
+impl Event for TextChangedEvent {}
+
+/// Fired on the node under the cursor when a mouse wheel or trackpad scrolls, and bubbled up until a widget stops it.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ScrollEvent {
+    pub delta: ScrollDelta,
+    pub modifiers: Modifiers
+}
+impl Event for ScrollEvent {}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::any::Any;
+    use std::cell::RefCell;
+    use std::fmt;
+    use std::rc::Rc;
+    use crate::{Event, EventControl, Widget, Style, Descendants, Result};
+
+    // Value of any type logged by a test, compared with values of the same type.
+    pub(crate) struct Logged {
+        value: Box<dyn Any>,
+        eq: fn(&dyn Any, &dyn Any) -> bool,
+        fmt: fn(&dyn Any, &mut fmt::Formatter<'_>) -> fmt::Result
+    }
+
+    pub(crate) fn logged<T: Any + PartialEq + fmt::Debug>(value: T) -> Logged {
+        Logged {
+            value: Box::new(value),
+            eq: |a, b| a.downcast_ref::<T>().is_some_and(|a| b.downcast_ref::<T>() == Some(a)),
+            fmt: |value, f| value.downcast_ref::<T>().unwrap().fmt(f)
+        }
+    }
+
+    impl PartialEq for Logged {
+        fn eq(&self, other: &Self) -> bool {
+            (self.eq)(&*self.value, &*other.value)
+        }
+    }
+
+    impl fmt::Debug for Logged {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            (self.fmt)(&*self.value, f)
+        }
+    }
+
+    // Values logged in order, shared by its clones.
+    #[derive(Clone, Default)]
+    pub(crate) struct EventLog(Rc<RefCell<Vec<Logged>>>);
+
+    impl EventLog {
+        pub fn push<T: Any + PartialEq + fmt::Debug>(&self, value: T) {
+            self.0.borrow_mut().push(logged(value));
+        }
+
+        // Takes the values logged so far.
+        pub fn take(&self) -> Vec<Logged> {
+            std::mem::take(&mut self.0.borrow_mut())
+        }
+    }
+
+    type Logger = Box<dyn Fn(&'static str, &EventControl, &EventLog)>;
+    type Reaction = Box<dyn FnMut(&mut EventControl)>;
+    type Spawner = Box<dyn Fn(&mut Descendants)>;
+
+    // Widget that logs events of the types chosen paired with its name, then reacts to events with a closure.
+    pub(crate) struct Recorder {
+        name: &'static str,
+        log: EventLog,
+        loggers: Vec<Logger>,
+        reaction: Option<Reaction>,
+        descendants: Option<Spawner>,
+        capture: bool
+    }
+
+    impl Recorder {
+        pub fn new(name: &'static str, log: &EventLog) -> Self {
+            Self { name, log: log.clone(), loggers: Vec::new(), reaction: None, descendants: None, capture: false }
+        }
+
+        // Logs events of a type as (name, event).
+        pub fn with<E: Event + Clone + PartialEq + fmt::Debug>(self) -> Self {
+            self.with_map(E::clone)
+        }
+
+        // Logs events of a type as (name, value), where the value is taken from the event.
+        pub fn with_map<E: Event, T: Any + PartialEq + fmt::Debug>(mut self, map: impl Fn(&E) -> T + 'static) -> Self {
+            self.loggers.push(Box::new(move |name, ctl, log| {
+                if let Some(event) = ctl.as_event::<E>() {
+                    log.push((name, map(event)));
+                }
+            }));
+            self
+        }
+
+        pub fn with_reaction(mut self, reaction: impl FnMut(&mut EventControl) + 'static) -> Self {
+            self.reaction = Some(Box::new(reaction));
+            self
+        }
+
+        pub fn with_descendants(mut self, descendants: impl Fn(&mut Descendants) + 'static) -> Self {
+            self.descendants = Some(Box::new(descendants));
+            self
+        }
+
+        // Also logs and reacts to events fired on descendants, in the capture phase.
+        pub fn with_capture(mut self) -> Self {
+            self.capture = true;
+            self
+        }
+
+        fn record(&mut self, ctl: &mut EventControl) {
+            for logger in &self.loggers {
+                logger(self.name, ctl, &self.log);
+            }
+            if let Some(reaction) = &mut self.reaction {
+                reaction(ctl);
+            }
+        }
+    }
+
+    impl Widget for Recorder {
+        fn descendants(&self, descendants: &mut Descendants) {
+            if let Some(spawn) = &self.descendants {
+                spawn(descendants);
+            }
+        }
+        fn event(&mut self, _style: &mut Style, _descendants: &mut Descendants, ctl: &mut EventControl) -> Result<()> {
+            self.record(ctl);
+            Ok(())
+        }
+        fn capture_event(&mut self, _style: &mut Style, _descendants: &mut Descendants, ctl: &mut EventControl) -> Result<()> {
+            if self.capture {
+                self.record(ctl);
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use std::time::Duration;
+use crate::{MouseButton, NodeId, Vec2};
+
+/// Thresholds used to recognize [`crate::ClickEvent`]s and [`crate::LongPressEvent`]s.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GestureConfig {
+    /// Maximum time between a click and the next press for them to count as a double or triple click.
+    pub click_time: Duration,
+    /// Maximum distance in pixels the cursor can move while pressed, or between clicks, before clicking starts over.
+    pub click_distance: f32,
+    /// Time a button has to be held without moving for it to be a long press.
+    pub long_press_time: Duration
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        Self {
+            click_time: Duration::from_millis(500),
+            click_distance: 4.0,
+            long_press_time: Duration::from_millis(800)
+        }
+    }
+}
+
+// Recognizes gestures from raw presses, releases and moves.
+#[derive(Clone, Default, Debug)]
+pub(crate) struct Gestures {
+    presses: Vec<GesturePress>,
+    last_click: Option<Click>
+}
+
+// Press of a button that may still end as a click or a long press.
+#[derive(Copy, Clone, Debug)]
+struct GesturePress {
+    button: MouseButton,
+    node_id: NodeId,
+    position: Vec2,
+    timestamp: Duration,
+    count: u32,
+    long_pressed: bool
+}
+
+// Last click, which the next one may continue.
+#[derive(Copy, Clone, Debug)]
+struct Click {
+    button: MouseButton,
+    node_id: NodeId,
+    position: Vec2,
+    timestamp: Duration,
+    count: u32
+}
+
+impl Gestures {
+
+    pub fn press(&mut self, config: &GestureConfig, button: MouseButton, node_id: NodeId, position: Vec2, timestamp: Duration) {
+        let count = match self.last_click {
+            Some(click) if
+                click.button == button &&
+                click.node_id == node_id &&
+                timestamp.saturating_sub(click.timestamp) <= config.click_time &&
+                click.position.distance(position) <= config.click_distance
+            => click.count + 1,
+            _ => 1
+        };
+        self.presses.retain(|press| press.button != button);
+        self.presses.push(GesturePress { button, node_id, position, timestamp, count, long_pressed: false });
+    }
+
+    // Cancels the presses the cursor moved too far from.
+    pub fn move_cursor(&mut self, config: &GestureConfig, position: Vec2) {
+        let count = self.presses.len();
+        self.presses.retain(|press| press.position.distance(position) <= config.click_distance);
+        if self.presses.len() != count {
+            self.last_click = None;
+        }
+    }
+
+    // Node and button of a press held long enough, the first time it is.
+    // Returns one press per call, so should be called until it returns none.
+    pub fn long_press(&mut self, config: &GestureConfig, timestamp: Duration) -> Option<(NodeId, MouseButton)> {
+        let press = self.presses.iter_mut().find(|press| {
+            !press.long_pressed && timestamp.saturating_sub(press.timestamp) >= config.long_press_time
+        })?;
+        press.long_pressed = true;
+        Some((press.node_id, press.button))
+    }
+
+    // Node clicked and the click count, if a button was released over the node it pressed.
+    pub fn release(&mut self, button: MouseButton, touching_id: Option<NodeId>, position: Vec2, timestamp: Duration) -> Option<(NodeId, u32)> {
+        let index = self.presses.iter().position(|press| press.button == button)?;
+        let press = self.presses.remove(index);
+        if press.long_pressed || touching_id != Some(press.node_id) {
+            self.last_click = None;
+            return None;
+        }
+        self.last_click = Some(Click { button, node_id: press.node_id, position, timestamp, count: press.count });
+        Some((press.node_id, press.count))
+    }
+
+    // Forgets the press of a button, like when it starts a drag.
+    pub fn cancel(&mut self, button: MouseButton) {
+        self.presses.retain(|press| press.button != button);
+        self.last_click = None;
+    }
+
+    pub fn remove(&mut self, node_id: NodeId) {
+        self.presses.retain(|press| press.node_id != node_id);
+        if self.last_click.is_some_and(|click| click.node_id == node_id) {
+            self.last_click = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+    use slotmap::SlotMap;
+    use crate::{MouseButton, NodeId, Vec2};
+    use super::{Gestures, GestureConfig};
+
+    #[test]
+    fn test_thresholds() {
+        let mut nodes = SlotMap::<NodeId, ()>::with_key();
+        let (a, b) = (nodes.insert(()), nodes.insert(()));
+        let config = GestureConfig::default();
+        let mut gestures = Gestures::default();
+        let millis = Duration::from_millis;
+        let click = |gestures: &mut Gestures, node_id, x: f32, time: u64| {
+            let position = Vec2::new(x, 0.0);
+            gestures.press(&config, MouseButton::Left, node_id, position, millis(time));
+            gestures.release(MouseButton::Left, Some(node_id), position, millis(time))
+        };
+
+        // Counts clicks within the click time and distance of the last, on the same node.
+        assert_eq!(Some((a, 1)), click(&mut gestures, a, 0.0, 0));
+        assert_eq!(Some((a, 2)), click(&mut gestures, a, 4.0, 500));
+        assert_eq!(Some((a, 1)), click(&mut gestures, a, 4.0, 1001));
+        assert_eq!(Some((a, 1)), click(&mut gestures, a, 8.1, 1100));
+        assert_eq!(Some((b, 1)), click(&mut gestures, b, 8.1, 1200));
+
+        // Moving further than the click distance while pressed cancels the click.
+        gestures.press(&config, MouseButton::Left, a, Vec2::ZERO, millis(2000));
+        gestures.move_cursor(&config, Vec2::new(4.0, 0.0));
+        assert_eq!(Some((a, 1)), gestures.release(MouseButton::Left, Some(a), Vec2::new(4.0, 0.0), millis(2000)));
+        gestures.press(&config, MouseButton::Left, a, Vec2::ZERO, millis(3000));
+        gestures.move_cursor(&config, Vec2::new(4.1, 0.0));
+        assert_eq!(None, gestures.release(MouseButton::Left, Some(a), Vec2::ZERO, millis(3000)));
+
+        // Holding for the long press time long presses once, instead of clicking.
+        gestures.press(&config, MouseButton::Right, a, Vec2::ZERO, millis(4000));
+        assert_eq!(None, gestures.long_press(&config, millis(4799)));
+        assert_eq!(Some((a, MouseButton::Right)), gestures.long_press(&config, millis(4800)));
+        assert_eq!(None, gestures.long_press(&config, millis(5000)));
+        assert_eq!(None, gestures.release(MouseButton::Right, Some(a), Vec2::ZERO, millis(5000)));
+    }
+}
@@ -1,4 +1,5 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
 
 use crate::Vec2;
 use slotmap::SlotMap;
@@ -8,6 +9,8 @@ use crate::extensions::VecExtensions;
 use crate::util::SliceIter;
 
 const EPS: f32 = 0.001;
+// Maximum number of queued events processed at once, before giving up on what is likely an event loop.
+const MAX_EVENT_ITERATIONS: usize = 10_000;
 type NodeIdVec = TinyVec<[NodeId; 32]>;
 
 /// Represents a graphical user interface, and a storage of [`Node`]s.
@@ -16,12 +19,33 @@ pub struct Gewy {
     storage: SlotMap<NodeId, Node>,
     root_id: NodeId,
     named_index: HashMap<Name, Vec<NodeId>>,
-    pub(crate) pressed_id: Option<NodeId>,
+    // Nodes pressed with each button held, in the order they were pressed.
+    pub(crate) pressed: Vec<Press>,
+    pub(crate) focused_id: Option<NodeId>,
+    // Events waiting to be fired on a node and bubbled up, oldest first.
+    queue: VecDeque<(DynEvent, NodeId)>,
+    // Node under the cursor and its parents, from the root.
+    hovered: Vec<NodeId>,
+    focus_visible: bool,
+    pub(crate) modifiers: Modifiers,
+    pub(crate) time: Duration,
+    // Time of the last tick, and the nodes and timers waiting for the next.
+    last_tick: Option<Duration>,
+    tick_ids: Vec<NodeId>,
+    timers: Vec<Timer>,
+    pub(crate) gestures: Gestures,
+    pub(crate) drag: Option<Drag>,
+    /// Thresholds for recognizing clicks and long presses.
+    pub gesture_config: GestureConfig,
     pub(crate) cursor: Cursor,
     pub(crate) next_cursor_icon: Option<CursorIcon>,
     pub translation: Vec2,
     pub scale: f32,
     pub round: bool,
+    /// Ring painted around the focused node when it was focused with the keyboard, or with [`Gewy::focus`].
+    pub focus_ring: Option<FocusRing>,
+    style_sheet: StyleSheet,
+    size: Vec2,
     painter: Painter
 }
 
@@ -34,12 +58,27 @@ impl Gewy {
             storage,
             root_id,
             named_index: HashMap::new(),
-            pressed_id: None,
+            pressed: Vec::new(),
+            focused_id: None,
+            queue: VecDeque::new(),
+            hovered: Vec::new(),
+            focus_visible: false,
+            modifiers: Modifiers::empty(),
+            time: Duration::ZERO,
+            last_tick: None,
+            tick_ids: Vec::new(),
+            timers: Vec::new(),
+            gestures: Gestures::default(),
+            drag: None,
+            gesture_config: GestureConfig::default(),
             cursor: Cursor::default(),
             next_cursor_icon: None,
             translation: Vec2::ZERO,
             scale: 1.0,
             round: true,
+            focus_ring: Some(FocusRing::default()),
+            style_sheet: StyleSheet::default(),
+            size: Vec2::ZERO,
             painter: Painter::new()
         };
         unsafe { slf.spawn_descendants(root_id) };
@@ -56,6 +95,21 @@ impl Gewy {
         self
     }
 
+    pub fn with_focus_ring(mut self, focus_ring: Option<FocusRing>) -> Self {
+        self.focus_ring = focus_ring;
+        self
+    }
+
+    pub fn with_gesture_config(mut self, gesture_config: GestureConfig) -> Self {
+        self.gesture_config = gesture_config;
+        self
+    }
+
+    pub fn with_style_sheet(mut self, style_sheet: StyleSheet) -> Self {
+        self.replace_style_sheet(style_sheet);
+        self
+    }
+
     /// Id of the root node.
     pub fn root_id(&self) -> NodeId {
         self.root_id
@@ -67,10 +121,11 @@ impl Gewy {
 
         // Stores node as a child of another.
         node.parent_id = Some(parent_id);
+        Self::apply_style_sheet(&self.style_sheet, self.size, &mut node, true);
         let node_id = self.storage.insert(node);
         let Some(parent) = self.storage.get_mut(parent_id) else {
             self.storage.remove(node_id);
-            return Err(GewyError::ParentNodeNotFound.into())
+            return Err(GewyError::ParentNodeNotFound)
         };
         parent.children_ids.push(node_id);
 
@@ -88,9 +143,7 @@ impl Gewy {
         if node_id == self.root_id {
             return None;
         }
-        let Some(mut node) = self.storage.remove(node_id) else {
-            return None;
-        };
+        let mut node = self.storage.remove(node_id)?;
         if let Some(parent_id) = node.parent_id {
             let parent = self.storage.get_mut(parent_id).unwrap();
             let child_idx = parent.children_ids.iter()
@@ -101,10 +154,25 @@ impl Gewy {
         for child_id in std::mem::take(&mut node.children_ids) {
             self.remove(child_id);
         };
+        self.pressed.retain(|press| press.node_id != node_id);
+        self.gestures.remove(node_id);
+        self.hovered.retain(|hovered_id| *hovered_id != node_id);
+        self.tick_ids.retain(|tick_id| *tick_id != node_id);
+        self.timers.retain(|timer| timer.node_id != node_id);
+        if self.drag.as_ref().is_some_and(|drag| drag.source_id == node_id) {
+            self.drag = None;
+        }
+        if let Some(drag) = self.drag.as_mut().filter(|drag| drag.target_id == Some(node_id)) {
+            drag.target_id = None;
+            drag.accepted = false;
+        }
+        if self.focused_id == Some(node_id) {
+            self.focused_id = None;
+        }
         Some(node)
     }
 
-    pub fn iter_named<'a>(&'a mut self, name: Name) -> impl Iterator<Item = &'a mut Node> + '_ {
+    pub fn iter_named(&mut self, name: Name) -> impl Iterator<Item = &mut Node> + '_ {
         let node_ids: &[NodeId] = unsafe { std::mem::transmute(self.ids_with_name(name)) };
         NodeIteratorMut {
             gewy: self,
@@ -114,7 +182,7 @@ impl Gewy {
     }
 
     pub fn ids_with_name(&mut self, name: Name) -> &[NodeId] {
-        let ids = self.named_index.entry(name).or_insert_with(|| Vec::new());
+        let ids = self.named_index.entry(name).or_default();
         &ids[..]
     }
 
@@ -146,14 +214,14 @@ impl Gewy {
     }
 
     // Gets ID of node touching 
-    fn node_touching<'a>(&'a self, node_id: NodeId, cursor: Vec2) -> Option<NodeId> {
+    fn node_touching(&self, node_id: NodeId, cursor: Vec2) -> Option<NodeId> {
         let node: &Node = unsafe { self.get_unsafe(node_id).unwrap() };
         for child_id in node.children_ids.iter().rev() {
             if let Some(id) = self.node_touching(*child_id, cursor) {
                 return Some(id);
             }
         }
-        if node.raw.padding_region().contains(cursor) {
+        if node.raw.border_region().contains(cursor) {
             Some(node_id)
         }
         else {
@@ -161,16 +229,215 @@ impl Gewy {
         }
     }
 
+    /// Gets a node with an unbounded lifetime.
+    ///
+    /// # Safety
+    /// The caller must ensure the node is not removed or mutably aliased while the reference is alive.
     pub unsafe fn get_unsafe<'a>(&self, node_id: NodeId) -> Result<&'a Node> {
         let node = self.storage.get(node_id).ok_or(GewyError::NodeNotFound);
         std::mem::transmute(node)
     }
 
+    /// Gets a node mutably with an unbounded lifetime.
+    ///
+    /// # Safety
+    /// The caller must ensure the node is not removed or aliased while the reference is alive.
     pub unsafe fn get_mut_unsafe<'a>(&mut self, node_id: NodeId) -> Result<&'a mut Node> {
         let node = self.storage.get_mut(node_id).ok_or(GewyError::NodeNotFound);
         std::mem::transmute(node)
     }
 
+    /// Node pressed with the first button that is still held, if any.
+    pub fn pressed_id(&self) -> Option<NodeId> {
+        self.pressed.first().map(|press| press.node_id)
+    }
+
+    /// Node that captured the pointer with a button that is still held, if any.
+    pub fn captured_id(&self) -> Option<NodeId> {
+        self.pressed.iter().find(|press| press.captured).map(|press| press.node_id)
+    }
+
+    /// True if the cursor is over the node, or over any of its children.
+    pub fn is_hovered(&self, node_id: NodeId) -> bool {
+        self.hovered.contains(&node_id)
+    }
+
+    // Hovers the node under the cursor and its parents.
+    // Fires an ExitEvent on each node no longer hovered, from the innermost, then an EnterEvent on each newly hovered node, from the outermost.
+    pub(crate) fn hover(&mut self, touching_id: Option<NodeId>) -> Result<()> {
+        let mut path = Vec::new();
+        let mut next_id = touching_id;
+        while let Some(node_id) = next_id {
+            path.push(node_id);
+            next_id = self.storage.get(node_id).and_then(|node| node.parent_id);
+        }
+        path.reverse();
+        let prev_path = std::mem::replace(&mut self.hovered, path.clone());
+        for &node_id in prev_path.iter().rev().filter(|node_id| !path.contains(node_id)) {
+            if self.storage.contains_key(node_id) {
+                self.fire(ExitEvent, node_id)?;
+            }
+        }
+        for &node_id in path.iter().filter(|node_id| !prev_path.contains(node_id)) {
+            if self.storage.contains_key(node_id) {
+                self.fire(EnterEvent, node_id)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Node that has keyboard focus, if any.
+    pub fn focused_id(&self) -> Option<NodeId> {
+        self.focused_id
+    }
+
+    /// Gives a node keyboard focus, firing an [`UnfocusEvent`] on the previously focused node and a [`FocusEvent`] on the node.
+    pub fn focus(&mut self, node_id: NodeId) -> Result<()> {
+        self.get(node_id)?;
+        self.focus_visible = true;
+        self.set_focus(Some(node_id));
+        self.process_events()
+    }
+
+    /// Takes keyboard focus away from the focused node, firing an [`UnfocusEvent`] on it.
+    pub fn unfocus(&mut self) -> Result<()> {
+        self.set_focus(None);
+        self.process_events()
+    }
+
+    // Hovers the node under the cursor again, after nodes moved beneath it.
+    // Errors from the events fired are logged, as nothing is there to pass them to.
+    fn rehover(&mut self) {
+        let touching_id = self.get_touching_id(self.cursor.position).filter(|_| self.cursor.inside);
+        if let Err(err) = self.hover(touching_id) {
+            log::error!("Hovering after layout caused an error: {err}");
+        }
+    }
+
+    /// Focuses the next focusable node in tab order, wrapping around to the first.
+    pub fn focus_next(&mut self) -> Result<()> {
+        self.focus_step(false)
+    }
+
+    /// Focuses the previous focusable node in tab order, wrapping around to the last.
+    pub fn focus_previous(&mut self) -> Result<()> {
+        self.focus_step(true)
+    }
+
+    fn focus_step(&mut self, backwards: bool) -> Result<()> {
+        let order = self.tab_order();
+        if order.is_empty() { return Ok(()) }
+        let current = self.focused_id.and_then(|focused_id| order.iter().position(|id| *id == focused_id));
+        let index = match (current, backwards) {
+            (Some(index), false) => (index + 1) % order.len(),
+            (Some(index), true) => (index + order.len() - 1) % order.len(),
+            (None, false) => 0,
+            (None, true) => order.len() - 1
+        };
+        self.focus(order[index])
+    }
+
+    // Focusable nodes that can be reached with tab, in the order they are reached.
+    fn tab_order(&self) -> Vec<NodeId> {
+        let mut order = Vec::new();
+        let mut stack = vec![self.root_id];
+        while let Some(node_id) = stack.pop() {
+            let node = &self.storage[node_id];
+            if node.style.focusable && node.style.tab_index >= 0 {
+                order.push(node_id);
+            }
+            stack.extend(node.children_ids.iter().rev());
+        }
+        order.sort_by_key(|node_id| match self.storage[*node_id].style.tab_index {
+            0 => i32::MAX,
+            tab_index => tab_index
+        });
+        order
+    }
+
+    // Focuses the closest focusable node at or above a pressed node, or removes focus if there is none.
+    pub(crate) fn focus_pressed(&mut self, mut node_id: NodeId) -> Result<()> {
+        loop {
+            let node = self.get(node_id)?;
+            if node.style.focusable {
+                self.focus_visible = false;
+                self.set_focus(Some(node_id));
+                break;
+            }
+            match node.parent_id {
+                Some(parent_id) => node_id = parent_id,
+                None => {
+                    self.set_focus(None);
+                    break;
+                }
+            }
+        }
+        self.process_events()
+    }
+
+    /// Advances time to now, usually the [`Clock::now`] of a [`Clock`], as [`InputMapping::set_time`] does.
+    /// Fires a [`TickEvent`] on each subscribed node, then a [`TimerEvent`] for each timer that ran out, from the earliest.
+    pub fn tick(&mut self, now: Duration) -> Result<()> {
+        let delta = self.last_tick.map_or(Duration::ZERO, |prev| now.saturating_sub(prev));
+        self.last_tick = Some(now);
+        self.mapping().set_time(now)?;
+        for node_id in self.tick_ids.clone() {
+            if self.storage.contains_key(node_id) {
+                self.handle(&TickEvent { now, delta }.into(), node_id, None, EventPhase::Target)?;
+            }
+        }
+
+        // Takes timers that ran out, keeping repeating ones for their next deadline after now.
+        let mut due: Vec<Timer> = Vec::new();
+        for timer in &mut self.timers {
+            if timer.deadline > now { continue }
+            due.push(*timer);
+            if let Some(interval) = timer.interval {
+                timer.deadline = timer.next_deadline(now, interval);
+            }
+        }
+        self.timers.retain(|timer| timer.deadline > now);
+        due.sort_by_key(|timer| timer.deadline);
+        for timer in due {
+            if self.storage.contains_key(timer.node_id) {
+                self.handle(&TimerEvent { id: timer.id, now }.into(), timer.node_id, None, EventPhase::Target)?;
+            }
+        }
+        self.process_events()
+    }
+
+    /// True if a widget started a drag that has not ended yet.
+    pub fn is_dragging(&self) -> bool {
+        self.drag.is_some()
+    }
+
+    /// Payload of the drag in progress, if any.
+    pub fn drag_payload(&self) -> Option<&DragPayload> {
+        self.drag.as_ref().map(|drag| &drag.payload)
+    }
+
+    /// Ends the drag in progress without dropping it.
+    /// Fires a [`DragLeaveEvent`] on the node under the cursor, and a [`DragEndEvent`] on the node that started the drag.
+    pub fn cancel_drag(&mut self) -> Result<()> {
+        let Some(drag) = self.drag.take() else { return Ok(()) };
+        if let Some(target_id) = drag.target_id {
+            self.fire_bubble(DragLeaveEvent { payload: drag.payload }, target_id)?;
+        }
+        self.fire_bubble(DragEndEvent { dropped: false }, drag.source_id)
+    }
+
+    /// Current time, passed to the last [`Gewy::tick`] or [`InputMapping::set_time`].
+    /// Timers started by widgets run out relative to it.
+    pub fn now(&self) -> Duration {
+        self.time
+    }
+
+    /// Attaches a closure to a node that handles events of type E fired on it, or bubbled up to it, after its widget does.
+    pub fn on<E: Event>(&mut self, node_id: NodeId, handler: impl FnMut(&E, &mut EventControl) + 'static) -> Result<()> {
+        self.get_mut(node_id)?.handlers.push(crate::handler(handler));
+        Ok(())
+    }
+
     /// Fires an event on whatever node is touching the point, and bubbles that event up to the root node.
     pub fn fire_bubble_at(&mut self, event: impl Event, point: Vec2) -> Result<()> {
         let Some(node_id) = self.node_touching(self.root_id, point) else {
@@ -181,70 +448,169 @@ impl Gewy {
     }
 
     /// Fires an event on a specific node, and bubbles that event up to the root node.
-    pub fn fire_bubble<'a>(&'a mut self, event: impl Into<DynEvent>, mut node_id: NodeId) -> Result<()> {
-        let event: DynEvent = event.into();
-        let mut node: &mut Node = unsafe { self.get_mut_unsafe(node_id)? };
-        let mut ctl = EventControl::new(&event, None);
-        loop {
-            
-            // Has Widget of current node handle event.
-            let style = &mut node.style;
-            let mut children = Descendants::new(node_id, self);
-            node.widget.event(style, &mut children, &mut ctl)?;
-            let stop_propagation = ctl.stop;
-
-            // Sets cursor
-            if let Some(icon) = ctl.cursor_icon {
-                self.next_cursor_icon = Some(icon);
+    /// Then processes the events this caused.
+    pub fn fire_bubble(&mut self, event: impl Into<DynEvent>, node_id: NodeId) -> Result<()> {
+        self.bubble(event.into(), node_id)?;
+        self.process_events()
+    }
+
+    /// Fires an event on a specific node, without capturing or bubbling it.
+    /// Then processes the events this caused.
+    pub fn fire(&mut self, event: impl Into<DynEvent>, node_id: NodeId) -> Result<()> {
+        self.handle(&event.into(), node_id, None, EventPhase::Target)?;
+        self.process_events()
+    }
+
+    /// Queues an event to be fired on a specific node and bubbled up the next time events are processed.
+    pub fn post(&mut self, event: impl Into<DynEvent>, node_id: NodeId) {
+        self.queue.push_back((event.into(), node_id));
+    }
+
+    /// Fires queued events in the order they were queued, including the events that they cause, until the queue is empty.
+    /// Events queued for nodes that were removed are dropped.
+    /// Fails with [`GewyError::EventLimit`], clearing the queue, if events keep causing more events.
+    pub fn process_events(&mut self) -> Result<()> {
+        let mut iterations = 0;
+        while let Some((event, node_id)) = self.queue.pop_front() {
+            iterations += 1;
+            if iterations > MAX_EVENT_ITERATIONS {
+                self.queue.clear();
+                return Err(GewyError::EventLimit);
+            }
+            if self.storage.contains_key(node_id) {
+                self.bubble(event, node_id)?;
             }
-        
-            // Fires outgoing events.
-            let Some(ancestor_id) = node.ancestor_id else { break };
-            Self::fire_outgoing_events(self, ctl, node_id, ancestor_id)?;
-            
-            // Bubbles up.
-            if stop_propagation { break }
-            ctl = EventControl::new(
-                &event,
-                Some(NodeOrigin {
-                    id: node_id,
-                    name: node.name
-                })
-            );
-            node_id = ancestor_id;
-            node = unsafe { self.get_mut_unsafe(node_id).unwrap() };        
         }
         Ok(())
     }
 
-    // Fires an event globally.
+    // Fires an event on a node, capturing it down from the root and bubbling it back up.
+    // Events fired by widgets are queued rather than fired.
+    // Returns true if a widget stopped it.
+    pub(crate) fn bubble(&mut self, event: DynEvent, node_id: NodeId) -> Result<bool> {
+
+        // Captures from the outermost ancestor.
+        let node = self.get(node_id)?;
+        let target = NodeOrigin { id: node_id, name: node.name };
+        let mut ancestor_ids = Vec::new();
+        let mut next_id = node.ancestor_id;
+        while let Some(ancestor_id) = next_id {
+            ancestor_ids.push(ancestor_id);
+            next_id = self.get(ancestor_id)?.ancestor_id;
+        }
+        for &ancestor_id in ancestor_ids.iter().rev() {
+            if self.handle(&event, ancestor_id, Some(target), EventPhase::Capture)? { return Ok(true) }
+        }
+
+        // Bubbles from the target.
+        if self.handle(&event, node_id, None, EventPhase::Target)? { return Ok(true) }
+        let mut origin = target;
+        for ancestor_id in ancestor_ids {
+            if self.handle(&event, ancestor_id, Some(origin), EventPhase::Bubble)? { return Ok(true) }
+            origin = NodeOrigin { id: ancestor_id, name: self.get(ancestor_id)?.name };
+        }
+        Ok(false)
+    }
+
+    // Has the widget of a node handle an event in a phase, returning true if it stopped it.
+    fn handle(&mut self, event: &DynEvent, node_id: NodeId, origin: Option<NodeOrigin>, phase: EventPhase) -> Result<bool> {
+        let node: &mut Node = unsafe { self.get_mut_unsafe(node_id)? };
+        let mut ctl = EventControl::new(event, origin);
+        ctl.phase = phase;
+        ctl.cursor_position = self.cursor.position - node.raw.border_region().position;
+        let mut children = Descendants::new(node_id, self);
+        match phase {
+            EventPhase::Capture => node.widget.capture_event(&mut node.style, &mut children, &mut ctl)?,
+            _ => {
+                node.widget.event(&mut node.style, &mut children, &mut ctl)?;
+                for handler in &mut node.handlers {
+                    handler(&mut ctl);
+                }
+            }
+        }
+        let stop = ctl.stop;
+
+        // Sets cursor
+        if let Some(icon) = ctl.cursor_icon {
+            self.next_cursor_icon = Some(icon);
+        }
+
+        // Queues outgoing events.
+        self.queue_outgoing_events(ctl, node_id, node.ancestor_id);
+        Ok(stop)
+    }
+
+    /// Fires an event on every node, without bubbling it.
+    /// Then processes the events this caused.
     pub fn fire_global(&mut self, event: impl Event) -> Result<()> {
         let event = DynEvent::new(event);
-        let storage: &mut SlotMap<NodeId, Node> = unsafe { std::mem::transmute(&mut self.storage) };
-        for (node_id, node) in storage.iter_mut() {
+        let node_ids: Vec<NodeId> = self.storage.keys().collect();
+        for node_id in node_ids {
+            if self.storage.contains_key(node_id) {
+                self.handle(&event, node_id, None, EventPhase::Target)?;
+            }
+        }
+        self.process_events()
+    }
 
-            // Has widget of current node handle event.
-            let mut children = Descendants { ancestor_id: node_id, parent_id: node_id, gewy: self };
-            let mut ctl = EventControl::new(&event, None);
-            node.widget.event(&mut node.style, &mut children, &mut ctl)?;
+    /// Style sheet applied to nodes by their class names.
+    pub fn style_sheet(&self) -> &StyleSheet {
+        &self.style_sheet
+    }
 
-            // Sets cursor
-            if let Some(icon) = ctl.cursor_icon {
-                self.next_cursor_icon = Some(icon);
-            }
+    /// Replaces the style sheet, and re-applies it to every node with class names.
+    /// Properties the new style sheet no longer declares go back to the values they had before any style sheet,
+    /// unless they were changed since the previous style sheet set them.
+    /// Fires an [`ExitEvent`] or [`EnterEvent`] on each node that the cursor is no longer, or now, over.
+    pub fn set_style_sheet(&mut self, style_sheet: StyleSheet) {
+        self.replace_style_sheet(style_sheet);
+        self.rehover();
+    }
 
-            // Handles output of EventControl.
-            let Some(ancestor_id) = node.ancestor_id else { break };
-            Self::fire_outgoing_events(self, ctl, node_id, ancestor_id)?;
+    fn replace_style_sheet(&mut self, style_sheet: StyleSheet) {
+        self.style_sheet = style_sheet;
+        for node in self.storage.values_mut() {
+            Self::apply_style_sheet(&self.style_sheet, self.size, node, true);
         }
-        Ok(())
+        self.layout();
     }
 
+    /// Resizes the viewport and lays out all nodes.
+    /// Re-applies media rules of the style sheet if the size crossed a breakpoint.
+    /// Fires an [`ExitEvent`] or [`EnterEvent`] on each node that the cursor is no longer, or now, over.
     pub fn resize(&mut self, size: Vec2) {
+        let media_changed = self.style_sheet.matching_media(self.size) != self.style_sheet.matching_media(size);
+        self.size = size;
+        if media_changed {
+            for node in self.storage.values_mut() {
+                Self::apply_style_sheet(&self.style_sheet, size, node, false);
+            }
+        }
         self.painter.resize(size, self.translation, self.scale);
+        self.layout();
+        self.rehover();
+    }
+
+    // Applies the style sheet's rules to a node, by its class names.
+    // Rules applied previously are undone first, except for properties changed since, and only media rules matching the viewport are applied.
+    fn apply_style_sheet(style_sheet: &StyleSheet, viewport_size: Vec2, node: &mut Node, apply_base: bool) {
+        StyleSheet::unapply(&mut node.media_applied, &mut node.style);
+        if apply_base {
+            StyleSheet::unapply(&mut node.sheet_applied, &mut node.style);
+            for class_name in &node.class_names {
+                style_sheet.apply_base(class_name, &mut node.style, &mut node.sheet_applied);
+            }
+        }
+        for class_name in &node.class_names {
+            style_sheet.apply_media(class_name, viewport_size, &mut node.style, &mut node.media_applied);
+        }
+    }
+
+    // Lays out all nodes within the current size.
+    fn layout(&mut self) {
         self.layout_children(
             &[self.root_id],
-            Rect::new(Vec2::ZERO, size),
+            Rect::new(Vec2::ZERO, self.size),
             Direction::default(),
             Justify::default(),
             Align::default()
@@ -260,9 +626,55 @@ impl Gewy {
     /// Paints the ui using the supplied painter.
     pub fn paint(&mut self) -> Vec<DrawCommand> {
         self.paint_node(self.root_id);
+        self.paint_focus_ring();
+        self.paint_drag_preview();
         std::mem::take(&mut self.painter.commands)
     }
 
+    // Paints the focus ring around the focused node, over every other node.
+    fn paint_focus_ring(&mut self) {
+        let Some(ring) = self.focus_ring.filter(|_| self.focus_visible) else { return };
+        let Some(node) = self.focused_id.and_then(|focused_id| self.storage.get(focused_id)) else { return };
+        let outset = ring.offset + ring.width;
+        let mut region = node.raw.border_region();
+        region.position -= Vec2::splat(outset);
+        region.size += Vec2::splat(outset * 2.0);
+        let c = node.raw.corners;
+        let mut corners = RawCorners {
+            top_left: c.top_left + outset,
+            top_right: c.top_right + outset,
+            bottom_right: c.bottom_right + outset,
+            bottom_left: c.bottom_left + outset
+        };
+        if self.round {
+            let unit = 1.0/self.scale;
+            region = region.round(unit);
+            corners = corners.round(unit);
+        }
+        let width = RawSides { top: ring.width, right: ring.width, bottom: ring.width, left: ring.width };
+        let state = self.painter.push();
+        self.painter
+            .set_translation(region.position)
+            .paint_border(region.size, corners, width, BorderColor::all(ring.color));
+        self.painter.pop(state);
+    }
+
+    // Paints the preview of the drag in progress under the cursor, over every other node.
+    fn paint_drag_preview(&mut self) {
+        let Some(preview) = self.drag.as_ref().and_then(|drag| drag.preview) else { return };
+        let mut position = self.cursor.position + preview.offset;
+        if self.round {
+            position = (position * self.scale).round() / self.scale;
+        }
+        let radius = preview.corner_radius;
+        let state = self.painter.push();
+        self.painter
+            .set_translation(position)
+            .set_color(preview.color)
+            .paint_rounded_rect(preview.size, radius, radius, radius, radius);
+        self.painter.pop(state);
+    }
+
     // Spawns descendants of the node specified using its widget.
     // Assumes the node has been spawned, but has no children.
     unsafe fn spawn_descendants(&mut self, node_id: NodeId) {
@@ -273,7 +685,7 @@ impl Gewy {
     }
 
     // Computes the raw regions of this node's children.
-    fn layout_children_of<'a>(&'a mut self, node_id: NodeId) {
+    fn layout_children_of(&mut self, node_id: NodeId) {
         
         // Gets children of node
         let node = self.get(node_id).unwrap();
@@ -315,6 +727,11 @@ impl Gewy {
             self.shrink_group(child_ids, group_basis_width, group_content_width, shrink_total, parent_size.x, is_reverse)
         };
 
+        // Content that wraps may need a different height at its final width.
+        if is_row {
+            self.fit_group(child_ids, parent_size.y);
+        }
+
         // Justifies and aligns children
         self.justify_group(child_ids, group_final_width, parent_size.x, parent_justify, is_reverse);
         self.align_group(child_ids, parent_size.y, parent_align);
@@ -325,6 +742,7 @@ impl Gewy {
             node.raw.region = node.raw.region.flip(!is_row);
             node.raw.region.position += parent_region.position;
             node.raw.margin = node.raw.margin.rotate_left(!is_row);
+            node.raw.border = node.raw.border.rotate_left(!is_row);
             node.raw.padding = node.raw.padding.rotate_left(!is_row);
             if !node.children().is_empty() {
                 self.layout_children_of(*child_id);
@@ -346,16 +764,37 @@ impl Gewy {
             let node = self.get_mut(*id).unwrap();
             let min_size = node.style.raw_min_size(parent_size, is_row);
             let max_size = node.style.raw_max_size(parent_size, is_row);
-            let padding_region_size = node.raw.padding_region_size();
+            let border_region_size = node.raw.border_region_size();
             node.raw.margin = node.style.raw_margin(parent_size, is_row);
+            node.raw.border = node.style.raw_border(parent_size, is_row);
             node.raw.padding = node.style.raw_padding(parent_size, is_row);
             node.raw.min_size = min_size;
             node.raw.max_size = max_size.max(min_size);
-            node.raw.corners = node.style.raw_corners(padding_region_size);
+            node.raw.corners = node.style.raw_corners(border_region_size);
 
-            // Sets initial size of node
-            let basis_size = node.style.raw_basis(parent_size.x, is_row);
-            let height = node.style.raw_height(parent_size.y, is_row);
+            // Sets initial size of node, using the size of its content where auto.
+            let mut basis_size = node.style.raw_basis(parent_size.x, is_row);
+            let mut height = node.style.raw_height(parent_size.y, is_row);
+            let (width_val, height_val) = if is_row {
+                (node.style.width, node.style.height)
+            }
+            else {
+                (node.style.height, node.style.width)
+            };
+            let is_auto_width = node.style.basis == Val::Auto && width_val == Val::Auto;
+            let max_content_size = Vec2::new(
+                if is_auto_width { parent_size.x - node.raw.frame_size().x } else { basis_size },
+                if height_val == Val::Auto { parent_size.y - node.raw.frame_size().y } else { height }
+            ).max(Vec2::ZERO);
+            if let Some(content_size) = node.widget.measure(&node.style, max_content_size.flip(!is_row)) {
+                let content_size = content_size.flip(!is_row);
+                if is_auto_width {
+                    basis_size = content_size.x;
+                }
+                if height_val == Val::Auto {
+                    height = content_size.y;
+                }
+            }
             node.raw.set_size(Vec2::new(basis_size, height));
 
             // Accumulates sums
@@ -494,6 +933,20 @@ impl Gewy {
         }
     }
 
+    // Re-measures the height of content with an auto height, given its final width.
+    // Only used in rows, as the width of nodes in columns is known before measuring.
+    fn fit_group(&mut self, group: &[NodeId], parent_height: f32) {
+        for id in group {
+            let node = self.get_mut(*id).unwrap();
+            if node.style.height != Val::Auto { continue }
+            let max_height = (parent_height - node.raw.frame_size().y).max(0.0);
+            let max_size = Vec2::new(node.raw.width(), max_height);
+            if let Some(content_size) = node.widget.measure(&node.style, max_size) {
+                node.raw.set_height(content_size.y);
+            }
+        }
+    }
+
     fn justify_group(
         &mut self,
         group: &[NodeId],
@@ -577,14 +1030,61 @@ impl Gewy {
         }
     }
 
-    fn fire_outgoing_events(&mut self, ctl: EventControl, node_id: NodeId, ancestor_id: NodeId) -> Result<()> {
-        if ctl.pressed {
-            self.pressed_id = Some(node_id);
+    fn queue_outgoing_events(&mut self, ctl: EventControl, node_id: NodeId, ancestor_id: Option<NodeId>) {
+        if ctl.pressed || ctl.captured {
+            let button = ctl.as_event::<PressEvent>().map_or(MouseButton::Left, |event| event.button);
+            self.pressed.retain(|press| press.button != button);
+            self.pressed.push(Press { button, node_id, captured: ctl.captured });
+        }
+        if ctl.focused {
+            self.focus_visible = false;
+            self.set_focus(Some(node_id));
+        }
+        if ctl.accepted && (ctl.is_event::<DragEnterEvent>() || ctl.is_event::<DragOverEvent>()) {
+            if let Some(drag) = &mut self.drag {
+                drag.accepted = true;
+            }
         }
+        if let Some((payload, preview)) = ctl.drag {
+            let button = self.pressed.iter()
+                .find(|press| press.node_id == node_id)
+                .map(|press| press.button)
+                .or(self.cursor.buttons.first().copied())
+                .unwrap_or(MouseButton::Left);
+            self.drag = Some(Drag { source_id: node_id, button, payload, preview, target_id: None, accepted: false });
+            self.gestures.cancel(button);
+        }
+        match ctl.ticks {
+            Some(true) if !self.tick_ids.contains(&node_id) => self.tick_ids.push(node_id),
+            Some(false) => self.tick_ids.retain(|tick_id| *tick_id != node_id),
+            _ => {}
+        }
+        for request in ctl.timers.iter().copied() {
+            let id = match request {
+                TimerRequest::Start { id, .. } | TimerRequest::Stop(id) => id
+            };
+            self.timers.retain(|timer| timer.node_id != node_id || timer.id != id);
+            if let TimerRequest::Start { id, delay, repeat } = request {
+                let deadline = self.time + delay;
+                let interval = repeat.then_some(delay.max(Duration::from_nanos(1)));
+                self.timers.push(Timer { node_id, id, deadline, interval });
+            }
+        }
+        let Some(ancestor_id) = ancestor_id else { return };
         for out_event in ctl.outgoing_events {
-            self.fire_bubble(out_event, ancestor_id)?;
+            self.post(out_event, ancestor_id);
+        }
+    }
+
+    // Moves keyboard focus, queueing an UnfocusEvent on the previously focused node and a FocusEvent on the next.
+    fn set_focus(&mut self, node_id: Option<NodeId>) {
+        if self.focused_id == node_id { return }
+        if let Some(prev_id) = std::mem::replace(&mut self.focused_id, node_id) {
+            self.post(UnfocusEvent, prev_id);
+        }
+        if let Some(node_id) = node_id {
+            self.post(FocusEvent, node_id);
         }
-        Ok(())
     }
 
     fn paint_node(&mut self, node_id: NodeId) {
@@ -594,24 +1094,42 @@ impl Gewy {
         let widget = &node.widget;
         let style = &node.style;
 
-        // Gets padding region
-        let mut paint_region = node.raw.padding_region();
+        // Fully transparent nodes are skipped along with their descendants.
+        // Translucent nodes are painted to their own layer.
+        let opacity = style.opacity.clamp(0.0, 1.0);
+        if opacity < EPS { return }
+        let layered = opacity < 1.0;
+        if layered {
+            self.painter.push_layer();
+        }
+
+        // Gets border region
+        let mut paint_region = node.raw.border_region();
         let mut corners = node.raw.corners;
+        let mut border = node.raw.border;
+        let mut padding = node.raw.padding;
         if self.round {
             let unit = 1.0/self.scale;
             paint_region = paint_region.round(unit);
             corners = corners.round(unit);
+            border = border.round(unit);
+            padding = padding.round(unit);
         }
 
-        // Paints widget in the padding region
+        // Paints widget in the border region
         let paint_size = paint_region.size;
         if paint_size.x > EPS && paint_size.y > EPS {
             let canvas = Canvas {
                 size: paint_size,
-                corners
+                corners,
+                border,
+                padding
             };
             let state = self.painter.push();
             self.painter.set_translation(paint_region.position);
+            if let Some(shadow) = style.shadow.filter(|shadow| !shadow.inset) {
+                self.painter.paint_shadow(paint_size, corners, shadow);
+            }
             widget.paint(style, &mut self.painter, canvas);
             self.painter.pop(state);
         }
@@ -621,6 +1139,9 @@ impl Gewy {
         for child_id in children {
             self.paint_node(*child_id);
         }
+        if layered {
+            self.painter.pop_layer(opacity);
+        }
     }
 }
 
@@ -640,14 +1161,15 @@ impl<'a> Iterator for NodeIteratorMut<'a> {
         self.index += 1;
         unsafe {
             let node = self.gewy.get_mut(node_id).unwrap();
-            Some(std::mem::transmute(node))
+            Some(std::mem::transmute::<&mut Node, &'a mut Node>(node))
         }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{Gewy, Node, NodeId};
+    use crate::event::test::{EventLog, Recorder, logged};
+    use crate::*;
 
     #[test]
     fn test_insert() {
@@ -689,4 +1211,281 @@ mod test {
         
         assert!(gewy.remove(root_id).is_none());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_border_layout() {
+        let mut gewy = Gewy::new(Node::default());
+        let root_id = gewy.root_id;
+        let style = Style {
+            width: Val::Px(100.0),
+            height: Val::Px(50.0),
+            margin: Sides::all(Val::Px(1.0)),
+            border: Border::all(Val::Px(2.0), Color::BLACK),
+            padding: Sides::all(Val::Px(3.0)),
+            ..Default::default()
+        };
+        let child_id = gewy.insert(root_id, Node::default().with_style(style)).unwrap();
+        gewy.resize(Vec2::new(200.0, 200.0));
+
+        let raw = gewy.get(child_id).unwrap().raw;
+        assert_eq!(Vec2::new(112.0, 62.0), raw.region.size);
+        assert_eq!(Vec2::new(110.0, 60.0), raw.border_region().size);
+        assert_eq!(
+            Rect::new(raw.region.position + Vec2::splat(6.0), Vec2::new(100.0, 50.0)),
+            raw.content_region()
+        );
+    }
+
+    #[test]
+    fn test_opacity_layers() {
+        let mut gewy = Gewy::new(Node::default());
+        let root_id = gewy.root_id;
+        let style = Style {
+            width: Val::Px(10.0),
+            height: Val::Px(10.0),
+            opacity: 0.5,
+            ..Default::default()
+        };
+        let child_id = gewy.insert(root_id, Node::default().with_style(style)).unwrap();
+        gewy.resize(Vec2::new(100.0, 100.0));
+
+        let commands = gewy.paint();
+        let pushes = commands.iter().filter(|c| **c == DrawCommand::PushLayer).count();
+        let pops = commands.iter().filter(|c| **c == DrawCommand::PopLayer { opacity: 0.5 }).count();
+        assert_eq!(1, pushes);
+        assert_eq!(1, pops);
+
+        gewy.get_mut(child_id).unwrap().style.opacity = 0.0;
+        let commands = gewy.paint();
+        let rects = commands.iter().filter(|c| matches!(c, DrawCommand::RoundedRect { .. })).count();
+        assert_eq!(1, rects);
+        assert!(!commands.contains(&DrawCommand::PushLayer));
+    }
+
+    #[test]
+    fn test_fill() {
+        let mut gewy = Gewy::new(Node::default());
+        let root_id = gewy.root_id;
+        let style = Style {
+            width: Val::Px(10.0),
+            height: Val::Px(20.0),
+            color: Color::RED,
+            ..Default::default()
+        };
+        let child_id = gewy.insert(root_id, Node::default().with_style(style)).unwrap();
+        gewy.resize(Vec2::new(100.0, 100.0));
+        let commands = gewy.paint();
+        assert!(commands.contains(&DrawCommand::Color(Color::RED)));
+
+        // The brush takes precedence over the color, and covers the node, which is centered vertically.
+        let brush = Brush::linear(Vec2::ZERO, Vec2::Y, [ColorStop::new(0.0, Color::RED), ColorStop::new(1.0, Color::BLUE)]);
+        gewy.get_mut(child_id).unwrap().style.brush = Some(brush);
+        let commands = gewy.paint();
+        let expected = Brush::linear(Vec2::new(0.0, 40.0), Vec2::new(0.0, 60.0), [ColorStop::new(0.0, Color::RED), ColorStop::new(1.0, Color::BLUE)]);
+        assert!(commands.contains(&DrawCommand::Brush(expected)));
+        assert!(!commands.contains(&DrawCommand::Color(Color::RED)));
+    }
+
+    #[test]
+    fn test_shadows() {
+        let mut gewy = Gewy::new(Node::default());
+        let root_id = gewy.root_id;
+        let shadow = Shadow::new(Vec2::new(2.0, 4.0), 3.0, 1.0, Color::BLACK);
+        let style = Style {
+            width: Val::Px(10.0),
+            height: Val::Px(20.0),
+            shadow: Some(shadow),
+            ..Default::default()
+        };
+        let child_id = gewy.insert(root_id, Node::default().with_style(style)).unwrap();
+        gewy.resize(Vec2::new(100.0, 100.0));
+
+        // Outset shadows are painted by the core at the node, before the node itself.
+        let commands = gewy.paint();
+        let shadows: Vec<_> = commands.windows(2).filter_map(|pair| match pair {
+            [DrawCommand::Translation(position), DrawCommand::Shadow { size, shadow, .. }] => Some((*position, *size, *shadow)),
+            _ => None
+        }).collect();
+        assert_eq!(vec![(Vec2::new(0.0, 40.0), Vec2::new(10.0, 20.0), shadow)], shadows);
+
+        // Shadows hidden under the node paint nothing.
+        let style = &mut gewy.get_mut(child_id).unwrap().style;
+        style.shadow = Some(Shadow::new(Vec2::ZERO, 0.0, 0.0, Color::BLACK));
+        let commands = gewy.paint();
+        assert!(!commands.iter().any(|c| matches!(c, DrawCommand::Shadow { .. })));
+    }
+
+    // Records focus changes of its node.
+    fn focus_recorder(name: &'static str, log: &EventLog) -> Recorder {
+        Recorder::new(name, log).with::<FocusEvent>().with::<UnfocusEvent>()
+    }
+
+    #[test]
+    fn test_focus() {
+        let log = EventLog::default();
+        let mut gewy = Gewy::new(Node::default());
+        let root_id = gewy.root_id;
+        let mut insert = |name: &'static str, focusable: bool, tab_index: i32| {
+            let style = Style { width: Val::Px(10.0), height: Val::Px(10.0), focusable, tab_index, ..Default::default() };
+            gewy.insert(root_id, Node::new(focus_recorder(name, &log), style)).unwrap()
+        };
+        let a = insert("a", true, 0);
+        let b = insert("b", true, 2);
+        insert("plain", false, 0);
+        insert("c", true, 1);
+        let hidden = insert("hidden", true, -1);
+        gewy.resize(Vec2::new(100.0, 100.0));
+        let focus = |name: &'static str| logged((name, FocusEvent));
+        let unfocus = |name: &'static str| logged((name, UnfocusEvent));
+
+        // Tabs through positive indices first, then tree order, and wraps around.
+        let mut mapping = gewy.mapping();
+        mapping.key_down(Key::Tab, Modifiers::empty()).unwrap();
+        mapping.key_down(Key::Tab, Modifiers::empty()).unwrap();
+        mapping.key_down(Key::Tab, Modifiers::empty()).unwrap();
+        mapping.key_down(Key::Tab, Modifiers::empty()).unwrap();
+        mapping.key_down(Key::Tab, Modifiers::SHIFT).unwrap();
+        mapping.key_down(Key::Tab, Modifiers::CONTROL).unwrap();
+        assert_eq!(Some(a), gewy.focused_id());
+        assert_eq!(
+            vec![
+                focus("c"), unfocus("c"), focus("b"), unfocus("b"), focus("a"),
+                unfocus("a"), focus("c"), unfocus("c"), focus("a")
+            ],
+            log.take()
+        );
+
+        // Keyboard focus paints a ring, and pressing focuses without one.
+        let ring = |commands: Vec<DrawCommand>| commands.iter().any(|c| matches!(c, DrawCommand::Border { .. }));
+        assert!(ring(gewy.paint()));
+        let center = gewy.get(hidden).unwrap().raw.border_region().center();
+        let mut mapping = gewy.mapping();
+        mapping.move_cursor(center).unwrap();
+        mapping.press(MouseButton::Left).unwrap();
+        assert_eq!(Some(hidden), gewy.focused_id());
+        assert!(!ring(gewy.paint()));
+
+        // Pressing outside of focusable nodes unfocuses.
+        let mut mapping = gewy.mapping();
+        mapping.move_cursor(Vec2::new(90.0, 90.0)).unwrap();
+        mapping.press(MouseButton::Left).unwrap();
+        assert_eq!(None, gewy.focused_id());
+        assert_eq!(vec![unfocus("a"), focus("hidden"), unfocus("hidden")], log.take());
+
+        // Widgets can keep tab for themselves.
+        let trap = focus_recorder("trap", &log).with_reaction(|ctl| {
+            if ctl.as_event::<KeyDownEvent>().is_some_and(|event| event.key == Key::Tab) {
+                ctl.stop();
+            }
+        });
+        let style = Style { focusable: true, ..Default::default() };
+        let trap = gewy.insert(gewy.root_id, Node::new(trap, style)).unwrap();
+        gewy.focus(trap).unwrap();
+        gewy.mapping().key_down(Key::Tab, Modifiers::empty()).unwrap();
+        assert_eq!(Some(trap), gewy.focused_id());
+        gewy.focus(b).unwrap();
+        gewy.remove(b);
+        assert_eq!(None, gewy.focused_id());
+        assert!(gewy.focus(b).is_err());
+    }
+
+    // Records the phases it sees focus events in, and spawns the next layers inside of it.
+    // The middle layer stops events in the phase given.
+    fn layer(log: &EventLog, name: &'static str, inner: &'static [&'static str], stop: Option<EventPhase>) -> Recorder {
+        let (phases, spawned) = (log.clone(), log.clone());
+        Recorder::new(name, log)
+            .with_capture()
+            .with_reaction(move |ctl| {
+                if !ctl.is_event::<FocusEvent>() { return }
+                phases.push((name, ctl.phase()));
+                if name == "middle" && stop == Some(ctl.phase()) {
+                    ctl.stop();
+                }
+            })
+            .with_descendants(move |descendants| {
+                let [name, inner @ ..] = inner else { return };
+                descendants.insert(Node::from_widget(layer(&spawned, name, inner, stop)));
+            })
+    }
+
+    #[test]
+    fn test_phases() {
+        for stop in [None, Some(EventPhase::Capture), Some(EventPhase::Bubble)] {
+            let log = EventLog::default();
+            let mut gewy = Gewy::new(Node::default());
+            let outer_id = gewy.insert(gewy.root_id, Node::from_widget(layer(&log, "outer", &["middle", "inner"], stop))).unwrap();
+            let middle_id = gewy.get(outer_id).unwrap().children()[0];
+            let inner_id = gewy.get(middle_id).unwrap().children()[0];
+
+            // Captures down to the target, then bubbles back up, unless stopped.
+            gewy.fire_bubble(FocusEvent, inner_id).unwrap();
+            let capture = |name: &'static str| logged((name, EventPhase::Capture));
+            let (target, bubble) = (logged(("inner", EventPhase::Target)), |name: &'static str| logged((name, EventPhase::Bubble)));
+            let expected = match stop {
+                None => vec![capture("outer"), capture("middle"), target, bubble("middle"), bubble("outer")],
+                Some(EventPhase::Capture) => vec![capture("outer"), capture("middle")],
+                Some(_) => vec![capture("outer"), capture("middle"), target, bubble("middle")]
+            };
+            assert_eq!(expected, log.take());
+        }
+    }
+
+    // Spawns a rect that handles focus events with a closure.
+    fn panel(log: &EventLog) -> Recorder {
+        let handled = log.clone();
+        Recorder::new("panel", log).with_descendants(move |descendants| {
+            let handled = handled.clone();
+            dsl::rect(dsl::on(move |event: &FocusEvent, ctl| {
+                handled.push(("rect", *event));
+                ctl.fire(UnfocusEvent);
+            }), descendants);
+        })
+    }
+
+    #[test]
+    fn test_handlers() {
+        let log = EventLog::default();
+        let mut gewy = Gewy::new(Node::default());
+        let panel_id = gewy.insert(gewy.root_id, Node::from_widget(panel(&log))).unwrap();
+        let rect_id = gewy.get(panel_id).unwrap().children()[0];
+        let handled = log.clone();
+        gewy.on(panel_id, move |event: &UnfocusEvent, _| handled.push(("panel", *event))).unwrap();
+
+        // Handlers only see their type of event, including events fired by descendants.
+        gewy.fire_bubble(FocusEvent, rect_id).unwrap();
+        gewy.fire_bubble(FocusEvent, panel_id).unwrap();
+        assert_eq!(vec![logged(("rect", FocusEvent)), logged(("panel", UnfocusEvent))], log.take());
+        gewy.remove(rect_id);
+        assert!(gewy.on::<FocusEvent>(rect_id, |_, _| {}).is_err());
+    }
+
+    #[test]
+    fn test_queue() {
+        let log = EventLog::default();
+        let mut gewy = Gewy::new(Node::default());
+        let panel_id = gewy.insert(gewy.root_id, Node::from_widget(panel(&log))).unwrap();
+        let rect_id = gewy.get(panel_id).unwrap().children()[0];
+        let other_id = gewy.insert(gewy.root_id, Node::default()).unwrap();
+        for node_id in [panel_id, other_id] {
+            let handled = log.clone();
+            gewy.on(node_id, move |event: &UnfocusEvent, _| handled.push((node_id, *event))).unwrap();
+        }
+
+        // Fires posted events in order, followed by the events they fired.
+        gewy.post(FocusEvent, rect_id);
+        gewy.post(UnfocusEvent, other_id);
+        assert!(log.take().is_empty());
+        gewy.process_events().unwrap();
+        assert_eq!(
+            vec![logged(("rect", FocusEvent)), logged((other_id, UnfocusEvent)), logged((panel_id, UnfocusEvent))],
+            log.take()
+        );
+
+        // Gives up on too many events.
+        for _ in 0..=super::MAX_EVENT_ITERATIONS {
+            gewy.post(FocusEvent, other_id);
+        }
+        assert_eq!(Err(GewyError::EventLimit), gewy.process_events());
+        assert_eq!(Ok(()), gewy.process_events());
+    }
+}
@@ -1,17 +1,108 @@
-use crate::{Gewy, Result, GewyEnterEvent, GewyExitEvent, PressEvent, ReleaseEvent, EnterEvent, ExitEvent, NodeId};
+use crate::{Gewy, Result, GewyEnterEvent, GewyExitEvent, PressEvent, ReleaseEvent, KeyDownEvent, KeyUpEvent, CharInputEvent, ScrollEvent, PointerMoveEvent, ClickEvent, LongPressEvent, DragEnterEvent, DragOverEvent, DragLeaveEvent, DropEvent, DragEndEvent, NodeId};
+use std::time::Duration;
 use crate::Vec2;
 
-#[derive(Copy, Clone, PartialEq, Default, Debug)]
+#[derive(Clone, PartialEq, Default, Debug)]
 pub(crate) struct Cursor {
     pub position: Vec2,
-    pub left_pressed: bool,
-    pub right_pressed: bool,
-    pub pressed_node: Option<NodeId>,
-    pub icon: CursorIcon
+    /// Buttons held, in the order they were pressed.
+    pub buttons: Vec<MouseButton>,
+    pub icon: CursorIcon,
+    /// True if the cursor moved over the window since it last exited.
+    pub inside: bool
 }
 
+// A node pressed with a button, which may have captured the pointer.
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
-pub enum MouseButton { Left, Right }
+pub(crate) struct Press {
+    pub button: MouseButton,
+    pub node_id: NodeId,
+    pub captured: bool
+}
+
+/// A button of a mouse, or a touch acting as the left button.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+    /// Any other button, like back and forward, numbered by the windowing backend.
+    Other(u16)
+}
+
+/// Distance scrolled by a mouse wheel or trackpad.
+/// Positive values scroll towards the top left of the content, revealing what is above and to the left.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ScrollDelta {
+    /// Lines and columns, usually from the notches of a mouse wheel.
+    Lines(Vec2),
+    /// Pixels, usually from a trackpad.
+    Pixels(Vec2)
+}
+
+impl ScrollDelta {
+
+    /// Distance in pixels, with lines converted using the line height specified.
+    pub fn to_pixels(self, line_height: f32) -> Vec2 {
+        match self {
+            Self::Lines(lines) => lines * line_height,
+            Self::Pixels(pixels) => pixels
+        }
+    }
+}
+
+/// A key on a keyboard, independent of the windowing backend.
+/// Keys produce the same value regardless of the modifiers held, so text should come from [`crate::CharInputEvent`]s instead.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Key {
+    A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+    Digit0, Digit1, Digit2, Digit3, Digit4, Digit5, Digit6, Digit7, Digit8, Digit9,
+    F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Backspace,
+    Delete,
+    Insert,
+    Enter,
+    Tab,
+    Escape,
+    Space,
+    Shift,
+    Control,
+    Alt,
+    Super,
+    /// Any key without a variant of its own.
+    Unknown
+}
+
+bitflags::bitflags! {
+    /// Modifier keys held during an event.
+    #[derive(Copy, Clone, Eq, PartialEq, Hash, Default, Debug)]
+    pub struct Modifiers: u8 {
+        const SHIFT = 1;
+        const CONTROL = 1 << 1;
+        const ALT = 1 << 2;
+        const SUPER = 1 << 3;
+    }
+}
+
+impl Modifiers {
+    pub fn shift(self) -> bool { self.contains(Self::SHIFT) }
+    pub fn control(self) -> bool { self.contains(Self::CONTROL) }
+    pub fn alt(self) -> bool { self.contains(Self::ALT) }
+    pub fn super_key(self) -> bool { self.contains(Self::SUPER) }
+
+    /// True if the key used for shortcuts is held, which is super on macOS and control elsewhere.
+    pub fn command(self) -> bool {
+        if cfg!(target_os = "macos") { self.super_key() } else { self.control() }
+    }
+}
 
 /// Different cursor icons, shamelessly copied from winit.
 #[derive(Copy, Clone, Eq, PartialEq, Default, Debug)]
@@ -69,73 +160,607 @@ impl<'a> InputMapping<'a> {
     }
 
     /// Alerts the [`Gewy`] that the cursor has exited window containing it.
-    /// Fires relevant events.
+    /// Fires relevant events, and an [`crate::ExitEvent`] on each hovered node.
     pub fn exit_cursor(&mut self) -> Result<()> {
-        self.gewy.fire_global(GewyExitEvent)?;
-        if let Some(pressed_node) = self.gewy.cursor.pressed_node {
-            self.gewy.fire_bubble(ReleaseEvent, pressed_node)?;
-            self.gewy.cursor.pressed_node = None;
-        }
-        Ok(())
+        self.gewy.cursor.inside = false;
+        self.gewy.hover(None)?;
+        self.drag_over(None)?;
+        self.gewy.fire_global(GewyExitEvent)
     }
 
     /// Moves the [`Gewy`]'s internal cursor.
-    /// Fires relevant events.
+    /// Fires an [`crate::ExitEvent`] or [`crate::EnterEvent`] on each node the cursor left or entered, including parents of the node under it.
+    /// These do not bubble.
     pub fn move_cursor(&mut self, position: Vec2) -> Result<()> {
         let touching_id = self.gewy.get_touching_id(position);
-        let prev_touching_id = self.gewy.get_touching_id(self.gewy.cursor.position);
-        match (touching_id, prev_touching_id) {
-            (None, Some(prev_id)) => self.gewy.fire_bubble(ExitEvent, prev_id)?,
-            (Some(node_id), None) => self.gewy.fire_bubble(EnterEvent, node_id)?,
-            (Some(node_id), Some(prev_id)) if node_id != prev_id => {
-                self.gewy.fire_bubble(ExitEvent, prev_id)?;
-                self.gewy.fire_bubble(EnterEvent, node_id)?;
-
-            },
-            _ => {}
-        }        
         self.gewy.cursor.position = position;
-        Ok(())
+        self.gewy.cursor.inside = true;
+        self.gewy.hover(touching_id)?;
+        self.gewy.gestures.move_cursor(&self.gewy.gesture_config, position);
+        if let Some(target_id) = self.gewy.captured_id().or(touching_id) {
+            let event = PointerMoveEvent {
+                position,
+                local_position: self.local_position(target_id),
+                modifiers: self.gewy.modifiers,
+                timestamp: self.gewy.time
+            };
+            self.gewy.fire_bubble(event, target_id)?;
+        }
+        self.drag_over(touching_id)
+    }
+
+    // Moves the drag in progress, if any, over the node under the cursor.
+    // Fires a DragLeaveEvent and a DragEnterEvent if that node changed, then a DragOverEvent.
+    fn drag_over(&mut self, touching_id: Option<NodeId>) -> Result<()> {
+        let Some(drag) = &mut self.gewy.drag else { return Ok(()) };
+        let payload = drag.payload.clone();
+        let prev_id = std::mem::replace(&mut drag.target_id, touching_id);
+        drag.accepted = false;
+        if prev_id != touching_id {
+            if let Some(prev_id) = prev_id.filter(|prev_id| self.gewy.get(*prev_id).is_ok()) {
+                self.gewy.fire_bubble(DragLeaveEvent { payload: payload.clone() }, prev_id)?;
+            }
+            if let Some(node_id) = touching_id {
+                self.gewy.fire_bubble(DragEnterEvent { payload: payload.clone(), position: self.gewy.cursor.position }, node_id)?;
+            }
+        }
+        let Some(node_id) = touching_id else { return Ok(()) };
+        if self.gewy.drag.is_none() { return Ok(()) }
+        let event = DragOverEvent {
+            payload,
+            position: self.gewy.cursor.position,
+            local_position: self.local_position(node_id)
+        };
+        self.gewy.fire_bubble(event, node_id)
     }
 
     /// Simulates a touch or a click on the [`Gewy`] at the current position of the internal cursor.
-    /// Fires relevant events.
+    /// Focuses the closest focusable node, and fires a [`PressEvent`] on the node under the cursor.
     pub fn press(&mut self, button: MouseButton) -> Result<()> {
-        match button {
-            MouseButton::Left => {
-                self.gewy.cursor.left_pressed = true;
-                self.gewy.fire_bubble_at(PressEvent, self.gewy.cursor.position)?;
-            },
-            MouseButton::Right => {
-                self.gewy.cursor.right_pressed = true;
-                self.gewy.fire_bubble_at(PressEvent, self.gewy.cursor.position)?;
-            }
+        if !self.gewy.cursor.buttons.contains(&button) {
+            self.gewy.cursor.buttons.push(button);
         }
-        Ok(())
+        let Some(node_id) = self.gewy.get_touching_id(self.gewy.cursor.position) else {
+            return self.gewy.unfocus();
+        };
+        self.gewy.gestures.press(&self.gewy.gesture_config, button, node_id, self.gewy.cursor.position, self.gewy.time);
+        self.gewy.focus_pressed(node_id)?;
+        let event = PressEvent {
+            button,
+            position: self.gewy.cursor.position,
+            local_position: self.local_position(node_id),
+            modifiers: self.gewy.modifiers,
+            timestamp: self.gewy.time
+        };
+        self.gewy.fire_bubble(event, node_id)
     }
 
     /// Simulates the release of a touch or click on the [`Gewy`] at the current position of the internal cursor.
-    /// Fires relevant events.
+    /// Fires a [`ReleaseEvent`] on the node that was pressed with the same button, if the cursor is still over it or it captured the pointer.
+    /// Then fires a [`ClickEvent`] on the node under the cursor if the button was pressed over it, and not long pressed.
+    /// If the button started a drag, first fires a [`DropEvent`] on the node under the cursor if it accepted the drag,
+    /// and a [`DragEndEvent`] on the node that started it.
     pub fn release(&mut self, button: MouseButton) -> Result<()> {
-        match button {
-            MouseButton::Left => {
-                self.gewy.cursor.left_pressed = false;
-                let Some(pressed_id) = self.gewy.pressed_id else { return Ok(()) };
-                self.gewy.pressed_id = None;
-                let Some(node_touching_id) = self.gewy.get_touching_id(self.gewy.cursor.position) else { return Ok(()) };
-                if node_touching_id != pressed_id { return Ok(()) }
-                self.gewy.fire_bubble(ReleaseEvent, pressed_id)?;
-            },
-            MouseButton::Right => {
-                self.gewy.cursor.right_pressed = false;
-                self.gewy.fire_global(ReleaseEvent)?;
+        self.gewy.cursor.buttons.retain(|held| *held != button);
+        let position = self.gewy.cursor.position;
+        if self.gewy.drag.as_ref().is_some_and(|drag| drag.button == button) {
+            self.drop()?;
+        }
+        let touching_id = self.gewy.get_touching_id(position);
+        if let Some(index) = self.gewy.pressed.iter().position(|press| press.button == button) {
+            let Press { node_id: pressed_id, captured, .. } = self.gewy.pressed.remove(index);
+            if captured || touching_id == Some(pressed_id) {
+                let event = ReleaseEvent {
+                    button,
+                    position,
+                    local_position: self.local_position(pressed_id),
+                    modifiers: self.gewy.modifiers,
+                    timestamp: self.gewy.time
+                };
+                self.gewy.fire_bubble(event, pressed_id)?;
             }
         }
+        let Some((clicked_id, count)) = self.gewy.gestures.release(button, touching_id, position, self.gewy.time) else { return Ok(()) };
+        let event = ClickEvent {
+            button,
+            position,
+            local_position: self.local_position(clicked_id),
+            modifiers: self.gewy.modifiers,
+            count,
+            timestamp: self.gewy.time
+        };
+        self.gewy.fire_bubble(event, clicked_id)
+    }
+
+    // Ends the drag in progress, dropping it on its target if accepted.
+    fn drop(&mut self) -> Result<()> {
+        let Some(drag) = self.gewy.drag.take() else { return Ok(()) };
+        let target_id = drag.target_id.filter(|_| drag.accepted);
+        if let Some(target_id) = target_id {
+            let event = DropEvent {
+                payload: drag.payload,
+                position: self.gewy.cursor.position,
+                local_position: self.local_position(target_id)
+            };
+            self.gewy.fire_bubble(event, target_id)?;
+        }
+        if self.gewy.get(drag.source_id).is_err() { return Ok(()) }
+        self.gewy.fire_bubble(DragEndEvent { dropped: target_id.is_some() }, drag.source_id)
+    }
+
+    /// Buttons currently held, in the order they were pressed.
+    pub fn buttons(&self) -> &[MouseButton] {
+        &self.gewy.cursor.buttons
+    }
+
+    /// Sets the time of the events that follow, and of timers started after, measured from the same point as [`Gewy::tick`].
+    /// Fires a [`LongPressEvent`] on each node pressed with a button that has now been held long enough.
+    pub fn set_time(&mut self, time: Duration) -> Result<()> {
+        self.gewy.time = time;
+        while let Some((node_id, button)) = self.gewy.gestures.long_press(&self.gewy.gesture_config, time) {
+            let event = LongPressEvent {
+                button,
+                position: self.gewy.cursor.position,
+                local_position: self.local_position(node_id),
+                modifiers: self.gewy.modifiers,
+                timestamp: time
+            };
+            self.gewy.fire_bubble(event, node_id)?;
+        }
         Ok(())
     }
 
+    // Position of the cursor relative to the border region of a node.
+    fn local_position(&self, node_id: NodeId) -> Vec2 {
+        let region = self.gewy.get(node_id).map(|node| node.raw.border_region()).unwrap_or_default();
+        self.gewy.cursor.position - region.position
+    }
+
+    /// Alerts the [`Gewy`] that a mouse wheel or trackpad scrolled, with the cursor at the position of the internal cursor.
+    /// Fires a [`ScrollEvent`] on the node under the cursor, with the modifiers currently held.
+    pub fn scroll(&mut self, delta: ScrollDelta) -> Result<()> {
+        let modifiers = self.gewy.modifiers;
+        self.gewy.fire_bubble_at(ScrollEvent { delta, modifiers }, self.gewy.cursor.position)
+    }
+
+    /// Alerts the [`Gewy`] that a key was pressed, or repeated while held.
+    /// Fires a [`KeyDownEvent`] on the focused node, or the root node if none is focused.
+    /// If no widget stops it, tab and shift+tab move focus to the next and previous focusable nodes.
+    /// While dragging, escape cancels the drag instead.
+    pub fn key_down(&mut self, key: Key, modifiers: Modifiers) -> Result<()> {
+        self.gewy.modifiers = modifiers;
+        if key == Key::Escape && self.gewy.is_dragging() {
+            return self.gewy.cancel_drag();
+        }
+        let stopped = self.gewy.bubble(KeyDownEvent { key, modifiers }.into(), self.key_target())?;
+        self.gewy.process_events()?;
+        let traverses = key == Key::Tab && (modifiers - Modifiers::SHIFT).is_empty();
+        if stopped || !traverses { return Ok(()) }
+        if modifiers.shift() {
+            self.gewy.focus_previous()
+        }
+        else {
+            self.gewy.focus_next()
+        }
+    }
+
+    /// Alerts the [`Gewy`] that a key was released.
+    /// Fires a [`KeyUpEvent`] on the focused node, or the root node if none is focused.
+    pub fn key_up(&mut self, key: Key, modifiers: Modifiers) -> Result<()> {
+        self.gewy.modifiers = modifiers;
+        self.gewy.fire_bubble(KeyUpEvent { key, modifiers }, self.key_target())
+    }
+
+    /// Alerts the [`Gewy`] that text was typed.
+    /// Fires a [`CharInputEvent`] on the focused node, or the root node if none is focused.
+    pub fn char_input(&mut self, text: impl Into<String>) -> Result<()> {
+        let text = text.into();
+        if text.is_empty() { return Ok(()) }
+        self.gewy.fire_bubble(CharInputEvent { text }, self.key_target())
+    }
+
+    /// Alerts the [`Gewy`] that the modifier keys held changed, without a key event.
+    pub fn set_modifiers(&mut self, modifiers: Modifiers) {
+        self.gewy.modifiers = modifiers;
+    }
+
+    /// Modifier keys currently held.
+    pub fn modifiers(&self) -> Modifiers {
+        self.gewy.modifiers
+    }
+
+    // Node that receives keyboard events.
+    fn key_target(&self) -> NodeId {
+        self.gewy.focused_id.unwrap_or(self.gewy.root_id())
+    }
+
     /// Takes any updates the the internal cursor icon.
     pub fn take_cursor_icon(&mut self) -> Option<CursorIcon> {
         std::mem::take(&mut self.gewy.next_cursor_icon)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+    use crate::event::test::{EventLog, Recorder, logged};
+    use crate::*;
+
+    #[test]
+    fn test_key_routing() {
+        let log = EventLog::default();
+        let recorder = |name| Recorder::new(name, &log)
+            .with::<KeyDownEvent>()
+            .with::<KeyUpEvent>()
+            .with::<CharInputEvent>()
+            .with_reaction(|ctl| if ctl.is_event::<PressEvent>() { ctl.focus() });
+        let mut gewy = Gewy::new(Node::from_widget(recorder("root")));
+        let style = Style { width: Val::Px(10.0), height: Val::Px(10.0), ..Default::default() };
+        gewy.insert(gewy.root_id(), Node::new(recorder("child"), style)).unwrap();
+        gewy.resize(Vec2::new(100.0, 100.0));
+
+        // Keys go to the root until a node is focused.
+        let mut mapping = gewy.mapping();
+        mapping.key_down(Key::A, Modifiers::SHIFT).unwrap();
+        mapping.move_cursor(Vec2::new(5.0, 50.0)).unwrap();
+        mapping.press(MouseButton::Left).unwrap();
+        mapping.key_up(Key::A, Modifiers::empty()).unwrap();
+        mapping.char_input("a").unwrap();
+        mapping.set_modifiers(Modifiers::CONTROL | Modifiers::ALT);
+        assert_eq!(Modifiers::CONTROL | Modifiers::ALT, mapping.modifiers());
+        assert_eq!(
+            vec![
+                logged(("root", KeyDownEvent { key: Key::A, modifiers: Modifiers::SHIFT })),
+                logged(("child", KeyUpEvent { key: Key::A, modifiers: Modifiers::empty() })),
+                logged(("child", CharInputEvent { text: "a".into() }))
+            ],
+            log.take()
+        );
+    }
+
+    #[test]
+    fn test_scroll() {
+        for stops in [false, true] {
+            let log = EventLog::default();
+            let inner_log = log.clone();
+            let outer = Recorder::new("outer", &log)
+                .with::<ScrollEvent>()
+                .with_descendants(move |descendants| {
+                    let inner = Recorder::new("inner", &inner_log)
+                        .with::<ScrollEvent>()
+                        .with_reaction(move |ctl| if stops && ctl.is_event::<ScrollEvent>() { ctl.stop() });
+                    let style = Style { width: Val::Px(20.0), height: Val::Px(20.0), ..Default::default() };
+                    descendants.insert(Node::new(inner, style));
+                });
+            let mut gewy = Gewy::new(Node::default());
+            let style = Style { width: Val::Px(50.0), height: Val::Px(50.0), ..Default::default() };
+            gewy.insert(gewy.root_id(), Node::new(outer, style)).unwrap();
+            gewy.resize(Vec2::new(50.0, 50.0));
+
+            // Bubbles from the node under the cursor, unless stopped.
+            let mut mapping = gewy.mapping();
+            mapping.move_cursor(Vec2::new(10.0, 25.0)).unwrap();
+            mapping.scroll(ScrollDelta::Lines(Vec2::new(0.0, -1.0))).unwrap();
+            mapping.move_cursor(Vec2::new(40.0, 5.0)).unwrap();
+            mapping.scroll(ScrollDelta::Pixels(Vec2::new(3.0, 0.0))).unwrap();
+            let lines = ScrollEvent { delta: ScrollDelta::Lines(Vec2::new(0.0, -1.0)), modifiers: Modifiers::empty() };
+            let pixels = ScrollEvent { delta: ScrollDelta::Pixels(Vec2::new(3.0, 0.0)), modifiers: Modifiers::empty() };
+            let expected = match stops {
+                false => vec![logged(("inner", lines)), logged(("outer", lines)), logged(("outer", pixels))],
+                true => vec![logged(("inner", lines)), logged(("outer", pixels))]
+            };
+            assert_eq!(expected, log.take());
+        }
+    }
+
+    #[test]
+    fn test_buttons() {
+        let log = EventLog::default();
+        let recorder = |name| Recorder::new(name, &log)
+            .with::<PressEvent>()
+            .with::<ReleaseEvent>()
+            .with_reaction(|ctl| if ctl.is_event::<PressEvent>() { ctl.press() });
+        let mut gewy = Gewy::new(Node::default());
+        let style = Style { width: Val::Px(10.0), height: Val::Px(100.0), ..Default::default() };
+        gewy.insert(gewy.root_id(), Node::new(recorder("a"), style.clone())).unwrap();
+        gewy.insert(gewy.root_id(), Node::new(recorder("b"), style)).unwrap();
+        gewy.resize(Vec2::new(100.0, 100.0));
+
+        // Pairs releases with the presses of the same button.
+        let mut mapping = gewy.mapping();
+        mapping.set_modifiers(Modifiers::SHIFT);
+        mapping.move_cursor(Vec2::new(2.0, 3.0)).unwrap();
+        mapping.press(MouseButton::Right).unwrap();
+        mapping.set_modifiers(Modifiers::empty());
+        mapping.press(MouseButton::Other(8)).unwrap();
+        assert_eq!(&[MouseButton::Right, MouseButton::Other(8)], mapping.buttons());
+        mapping.set_time(Duration::from_millis(5)).unwrap();
+        mapping.release(MouseButton::Other(8)).unwrap();
+        mapping.release(MouseButton::Left).unwrap();
+
+        // Releases outside of the pressed node are not fired.
+        mapping.move_cursor(Vec2::new(15.0, 3.0)).unwrap();
+        mapping.press(MouseButton::Middle).unwrap();
+        mapping.release(MouseButton::Right).unwrap();
+        mapping.release(MouseButton::Middle).unwrap();
+        assert!(mapping.buttons().is_empty());
+        assert_eq!(None, gewy.pressed_id());
+        let press = |button, x, modifiers, millis| PressEvent {
+            button,
+            position: Vec2::new(x, 3.0),
+            local_position: Vec2::new(x % 10.0, 3.0),
+            modifiers,
+            timestamp: Duration::from_millis(millis)
+        };
+        let release = |button, x, millis| ReleaseEvent {
+            button,
+            position: Vec2::new(x, 3.0),
+            local_position: Vec2::new(x % 10.0, 3.0),
+            modifiers: Modifiers::empty(),
+            timestamp: Duration::from_millis(millis)
+        };
+        assert_eq!(
+            vec![
+                logged(("a", press(MouseButton::Right, 2.0, Modifiers::SHIFT, 0))),
+                logged(("a", press(MouseButton::Other(8), 2.0, Modifiers::empty(), 0))),
+                logged(("a", release(MouseButton::Other(8), 2.0, 5))),
+                logged(("b", press(MouseButton::Middle, 15.0, Modifiers::empty(), 5))),
+                logged(("b", release(MouseButton::Middle, 15.0, 5)))
+            ],
+            log.take()
+        );
+    }
+
+    #[test]
+    fn test_capture() {
+        let log = EventLog::default();
+
+        // Captures the pointer when pressed with the left button, else only presses.
+        let slider = Recorder::new("slider", &log)
+            .with_map(|event: &PointerMoveEvent| event.local_position)
+            .with_map(|event: &ReleaseEvent| event.button)
+            .with_reaction(|ctl| match ctl.as_event::<PressEvent>().map(|event| event.button) {
+                Some(MouseButton::Left) => ctl.capture(),
+                Some(_) => ctl.press(),
+                None => {}
+            });
+        let mut gewy = Gewy::new(Node::default());
+        let style = Style { width: Val::Px(10.0), height: Val::Px(100.0), ..Default::default() };
+        gewy.insert(gewy.root_id(), Node::new(slider, style)).unwrap();
+        gewy.resize(Vec2::new(100.0, 100.0));
+
+        // Moves are fired on the hovered node.
+        let mut mapping = gewy.mapping();
+        mapping.move_cursor(Vec2::new(2.0, 3.0)).unwrap();
+        mapping.move_cursor(Vec2::new(50.0, 3.0)).unwrap();
+
+        // Captured nodes receive moves and releases outside of their bounds.
+        mapping.move_cursor(Vec2::new(5.0, 3.0)).unwrap();
+        mapping.press(MouseButton::Left).unwrap();
+        mapping.move_cursor(Vec2::new(30.0, 3.0)).unwrap();
+        mapping.release(MouseButton::Left).unwrap();
+        assert_eq!(None, gewy.captured_id());
+
+        // Pressed nodes do not.
+        let mut mapping = gewy.mapping();
+        mapping.move_cursor(Vec2::new(5.0, 3.0)).unwrap();
+        mapping.press(MouseButton::Right).unwrap();
+        mapping.move_cursor(Vec2::new(30.0, 3.0)).unwrap();
+        mapping.release(MouseButton::Right).unwrap();
+        assert_eq!(
+            vec![
+                logged(("slider", Vec2::new(2.0, 3.0))),
+                logged(("slider", Vec2::new(5.0, 3.0))),
+                logged(("slider", Vec2::new(30.0, 3.0))),
+                logged(("slider", MouseButton::Left)),
+                logged(("slider", Vec2::new(5.0, 3.0)))
+            ],
+            log.take()
+        );
+    }
+
+    #[test]
+    fn test_gestures() {
+        let log = EventLog::default();
+        let recorder = Recorder::new("node", &log)
+            .with_map(|event: &ClickEvent| (event.button, event.count))
+            .with_map(|event: &LongPressEvent| (event.button, event.timestamp));
+        let mut gewy = Gewy::new(Node::default());
+        let style = Style { width: Val::Px(50.0), height: Val::Px(100.0), ..Default::default() };
+        gewy.insert(gewy.root_id(), Node::new(recorder, style)).unwrap();
+        gewy.resize(Vec2::new(100.0, 100.0));
+        let mut mapping = gewy.mapping();
+        let click = |mapping: &mut InputMapping, millis: u64, button: MouseButton| {
+            mapping.set_time(Duration::from_millis(millis)).unwrap();
+            mapping.press(button).unwrap();
+            mapping.release(button).unwrap();
+        };
+
+        // Counts clicks in quick succession, with the same button and close by.
+        mapping.move_cursor(Vec2::new(10.0, 10.0)).unwrap();
+        click(&mut mapping, 0, MouseButton::Left);
+        click(&mut mapping, 300, MouseButton::Left);
+        mapping.move_cursor(Vec2::new(12.0, 10.0)).unwrap();
+        click(&mut mapping, 600, MouseButton::Left);
+        click(&mut mapping, 700, MouseButton::Right);
+        click(&mut mapping, 2000, MouseButton::Right);
+        click(&mut mapping, 2100, MouseButton::Right);
+        mapping.move_cursor(Vec2::new(30.0, 10.0)).unwrap();
+        click(&mut mapping, 2200, MouseButton::Right);
+
+        // Moving away while pressed cancels the click.
+        mapping.press(MouseButton::Left).unwrap();
+        mapping.move_cursor(Vec2::new(40.0, 10.0)).unwrap();
+        mapping.release(MouseButton::Left).unwrap();
+
+        // Holding long enough is a long press instead of a click.
+        mapping.set_time(Duration::from_millis(3000)).unwrap();
+        mapping.press(MouseButton::Left).unwrap();
+        mapping.set_time(Duration::from_millis(3500)).unwrap();
+        mapping.set_time(Duration::from_millis(3800)).unwrap();
+        mapping.set_time(Duration::from_millis(3900)).unwrap();
+        mapping.release(MouseButton::Left).unwrap();
+
+        // Tracks the press of each button separately.
+        mapping.set_time(Duration::from_millis(5000)).unwrap();
+        mapping.press(MouseButton::Left).unwrap();
+        mapping.press(MouseButton::Right).unwrap();
+        mapping.release(MouseButton::Right).unwrap();
+        mapping.release(MouseButton::Left).unwrap();
+        mapping.set_time(Duration::from_millis(6000)).unwrap();
+        mapping.press(MouseButton::Left).unwrap();
+        mapping.set_time(Duration::from_millis(6100)).unwrap();
+        mapping.press(MouseButton::Right).unwrap();
+        mapping.set_time(Duration::from_millis(7000)).unwrap();
+        mapping.release(MouseButton::Left).unwrap();
+        mapping.release(MouseButton::Right).unwrap();
+        let click = |button: MouseButton, count: u32| logged(("node", (button, count)));
+        let long_press = |button: MouseButton, millis| logged(("node", (button, Duration::from_millis(millis))));
+        assert_eq!(
+            vec![
+                click(MouseButton::Left, 1),
+                click(MouseButton::Left, 2),
+                click(MouseButton::Left, 3),
+                click(MouseButton::Right, 1),
+                click(MouseButton::Right, 1),
+                click(MouseButton::Right, 2),
+                click(MouseButton::Right, 1),
+                long_press(MouseButton::Left, 3800),
+                click(MouseButton::Right, 1),
+                click(MouseButton::Left, 1),
+                long_press(MouseButton::Left, 7000),
+                long_press(MouseButton::Right, 7000)
+            ],
+            log.take()
+        );
+    }
+
+    #[test]
+    fn test_hover() {
+        let log = EventLog::default();
+        let recorder = |name| Recorder::new(name, &log).with::<EnterEvent>().with::<ExitEvent>();
+        let mut gewy = Gewy::new(Node::default());
+        let style = Style { width: Val::Px(50.0), height: Val::Px(100.0), ..Default::default() };
+        let outer_id = gewy.insert(gewy.root_id(), Node::new(recorder("outer"), style.clone())).unwrap();
+        let other_id = gewy.insert(gewy.root_id(), Node::new(recorder("other"), style)).unwrap();
+        let style = Style { width: Val::Px(10.0), height: Val::Px(10.0), ..Default::default() };
+        let inner_id = gewy.insert(outer_id, Node::new(recorder("inner"), style)).unwrap();
+        gewy.resize(Vec2::new(100.0, 100.0));
+
+        // Only nodes whose hover state changed are notified.
+        let mut mapping = gewy.mapping();
+        mapping.move_cursor(Vec2::new(5.0, 50.0)).unwrap();
+        mapping.move_cursor(Vec2::new(6.0, 50.0)).unwrap();
+        assert!(gewy.is_hovered(gewy.root_id()) && gewy.is_hovered(outer_id) && gewy.is_hovered(inner_id));
+        let mut mapping = gewy.mapping();
+        mapping.move_cursor(Vec2::new(20.0, 50.0)).unwrap();
+        assert!(gewy.is_hovered(outer_id) && !gewy.is_hovered(inner_id));
+        gewy.mapping().move_cursor(Vec2::new(70.0, 50.0)).unwrap();
+        assert_eq!(
+            vec![
+                logged(("outer", EnterEvent)),
+                logged(("inner", EnterEvent)),
+                logged(("inner", ExitEvent)),
+                logged(("outer", ExitEvent)),
+                logged(("other", EnterEvent))
+            ],
+            log.take()
+        );
+
+        // Follows nodes moving under the cursor, until it exits.
+        gewy.resize(Vec2::new(60.0, 100.0));
+        assert!(!gewy.is_hovered(other_id) && !gewy.is_hovered(gewy.root_id()));
+        gewy.resize(Vec2::new(100.0, 100.0));
+        assert!(gewy.is_hovered(other_id));
+        gewy.mapping().exit_cursor().unwrap();
+        gewy.resize(Vec2::new(120.0, 100.0));
+        assert!(!gewy.is_hovered(other_id) && !gewy.is_hovered(gewy.root_id()));
+        assert_eq!(
+            vec![logged(("other", ExitEvent)), logged(("other", EnterEvent)), logged(("other", ExitEvent))],
+            log.take()
+        );
+    }
+
+    #[test]
+    fn test_drag() {
+        let log = EventLog::default();
+        let payload = |payload: &DragPayload| payload.get::<u32>().copied();
+
+        // Drags a number once pressed and moved.
+        let mut pressed = false;
+        let source = Recorder::new("source", &log)
+            .with::<DragEndEvent>()
+            .with_reaction(move |ctl| {
+                if ctl.is_event::<PressEvent>() {
+                    pressed = true;
+                    ctl.capture();
+                }
+                else if ctl.is_event::<PointerMoveEvent>() && std::mem::take(&mut pressed) {
+                    ctl.start_drag(7_u32, Some(DragPreview::new(Vec2::splat(5.0), Color::RED)));
+                }
+            });
+
+        // Accepts numbers dropped on it.
+        let target = Recorder::new("target", &log)
+            .with_map(move |event: &DragEnterEvent| ("enter", payload(&event.payload)))
+            .with_map(|_: &DragLeaveEvent| "leave")
+            .with_map(move |event: &DropEvent| ("drop", payload(&event.payload)))
+            .with_reaction(|ctl| {
+                if ctl.as_event::<DragOverEvent>().is_some_and(|event| event.payload.is::<u32>()) {
+                    ctl.accept_drop();
+                }
+            });
+        let mut gewy = Gewy::new(Node::default());
+        let style = Style { width: Val::Px(10.0), height: Val::Px(100.0), ..Default::default() };
+        gewy.insert(gewy.root_id(), Node::new(source, style.clone())).unwrap();
+        gewy.insert(gewy.root_id(), Node::new(target, style)).unwrap();
+        gewy.resize(Vec2::new(100.0, 100.0));
+        let start_drag = |gewy: &mut Gewy| {
+            let mut mapping = gewy.mapping();
+            mapping.move_cursor(Vec2::new(5.0, 50.0)).unwrap();
+            mapping.press(MouseButton::Left).unwrap();
+            mapping.move_cursor(Vec2::new(15.0, 50.0)).unwrap();
+            assert!(gewy.is_dragging());
+        };
+        let rects = |commands: Vec<DrawCommand>| commands.iter().filter(|c| matches!(c, DrawCommand::RoundedRect { .. })).count();
+
+        // Drops on nodes that accepted, painting a preview until then.
+        start_drag(&mut gewy);
+        let dragging_rects = rects(gewy.paint());
+        gewy.mapping().release(MouseButton::Left).unwrap();
+        assert!(!gewy.is_dragging());
+        assert_eq!(dragging_rects, rects(gewy.paint()) + 1);
+
+        // Ends without dropping elsewhere, or when cancelled.
+        start_drag(&mut gewy);
+        let mut mapping = gewy.mapping();
+        mapping.move_cursor(Vec2::new(50.0, 50.0)).unwrap();
+        mapping.release(MouseButton::Left).unwrap();
+        start_drag(&mut gewy);
+        gewy.mapping().key_down(Key::Escape, Modifiers::empty()).unwrap();
+        gewy.mapping().release(MouseButton::Left).unwrap();
+
+        // Ends with the button that pressed the source, not other buttons held.
+        let mut mapping = gewy.mapping();
+        mapping.move_cursor(Vec2::new(50.0, 50.0)).unwrap();
+        mapping.press(MouseButton::Right).unwrap();
+        start_drag(&mut gewy);
+        gewy.mapping().release(MouseButton::Right).unwrap();
+        assert!(gewy.is_dragging());
+        gewy.mapping().release(MouseButton::Left).unwrap();
+        assert!(!gewy.is_dragging());
+        let entered = || logged(("target", ("enter", Some(7_u32))));
+        let dropped = || logged(("target", ("drop", Some(7_u32))));
+        let left = || logged(("target", "leave"));
+        let ended = |dropped| logged(("source", DragEndEvent { dropped }));
+        assert_eq!(
+            vec![
+                entered(), dropped(), ended(true),
+                entered(), left(), ended(false),
+                entered(), left(), ended(false),
+                entered(), dropped(), ended(true)
+            ],
+            log.take()
+        );
+    }
+}
@@ -4,12 +4,18 @@ mod style;
 mod widget;
 mod event;
 mod color;
+mod brush;
 mod paint;
 mod err;
 mod math;
 mod raw;
 mod extensions;
 mod input;
+mod gesture;
+mod time;
+mod drag;
+mod sheet;
+mod text;
 
 // Extended APIs
 pub mod util;
@@ -26,11 +32,17 @@ pub use style::*;
 pub use widget::*;
 pub use event::*;
 pub use color::*;
+pub use brush::*;
 pub use paint::*;
 pub use math::*;
 pub use err::*;
 pub use raw::*;
 pub use input::*;
+pub use gesture::*;
+pub use time::*;
+pub use drag::*;
+pub use sheet::*;
+pub use text::*;
 
 // Re-exports external dependencies
 pub use glam::*;
\ No newline at end of file
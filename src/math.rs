@@ -29,6 +29,10 @@ impl Rect {
         }
     }
 
+    pub fn center(&self) -> Vec2 {
+        self.position + self.size / 2.0
+    }
+
     pub fn contains(&self, point: Vec2) -> bool {
         let tl = self.position;
         let br = self.position + self.size;
@@ -1,5 +1,5 @@
 use slotmap::new_key_type;
-use crate::{Style, Widget, Pane, Raw};
+use crate::{Style, Widget, Pane, Raw, Applied, Event, EventControl, Handler};
 
 /// Name of a [`Node`].
 pub type Name = u16;
@@ -13,7 +13,11 @@ new_key_type! {
 /// Stores a [`Widget`] and a [`Style`], and may or may not have child [`Node`]s.
 pub struct Node {
     pub(crate) widget: Box<dyn Widget>,
+    pub(crate) handlers: Vec<Handler>,
     pub(crate) name: Option<Name>,
+    pub(crate) class_names: Vec<String>,
+    pub(crate) sheet_applied: Vec<Applied>,
+    pub(crate) media_applied: Vec<Applied>,
     pub(crate) style: Style,
     pub(crate) children_ids: Vec<NodeId>,
     pub(crate) parent_id: Option<NodeId>,
@@ -26,7 +30,11 @@ impl Default for Node {
         Self {
             style: Default::default(),
             widget: Box::new(Pane),
+            handlers: Vec::new(),
             name: None,
+            class_names: Vec::new(),
+            sheet_applied: Vec::new(),
+            media_applied: Vec::new(),
             children_ids: Vec::new(),
             parent_id: None,
             ancestor_id: None,
@@ -56,16 +64,27 @@ impl Node {
         self.name = name;
         self
     }
+    /// Attaches a closure that handles events of type E fired on the node, or bubbled up to it, after its widget does.
+    pub fn with_handler<E: Event>(mut self, handler: impl FnMut(&E, &mut EventControl) + 'static) -> Self {
+        self.handlers.push(crate::handler(handler));
+        self
+    }
     pub fn with_style(mut self, style: Style) -> Self {
         self.style = style;
         self
     }
+    /// Adds the name of a [`crate::StyleSheet`] class to apply to the node's style.
+    pub fn with_class_name(mut self, class_name: impl Into<String>) -> Self {
+        self.class_names.push(class_name.into());
+        self
+    }
     pub fn style(&self) -> &Style { &self.style }
     pub fn style_mut(&mut self) -> &Style { &mut self.style }
     pub fn widget(&self) -> &dyn Widget { self.widget.as_ref() }
     pub fn widget_mut(&mut self) -> &mut dyn Widget { self.widget.as_mut() }
     pub fn name(&self) -> Option<Name> { self.name }
     pub fn name_mut(&mut self) -> Option<&mut Name> { self.name.as_mut() }
+    pub fn class_names(&self) -> &[String] { &self.class_names }
     pub fn children(&self) -> &[NodeId] { &self.children_ids }
     pub fn parent(&self) -> Option<NodeId> { self.parent_id }
 }
\ No newline at end of file
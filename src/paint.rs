@@ -1,4 +1,4 @@
-use crate::Color;
+use crate::{Color, Brush, RawCorners, RawSides, BorderColor, Shadow, GlyphRun};
 use crate::Vec2;
 use std::fmt::Debug;
 
@@ -7,8 +7,9 @@ use std::fmt::Debug;
 /// Raw paint commands are to be implemented by a backend.
 #[derive(Clone, Default, Debug)]
 pub struct Painter {
-    color: Color,
+    brush: Brush,
     translation: Vec2,
+    location: Vec2,
     pub(crate) commands: Vec<DrawCommand>
 }
 
@@ -17,8 +18,9 @@ impl Painter {
     /// Creates empty painter.
     pub fn new() -> Self {
         Self {
-            color: Color::WHITE,
+            brush: Brush::Solid(Color::WHITE),
             translation: Vec2::ZERO,
+            location: Vec2::ZERO,
             commands: Vec::new()
         }
     }
@@ -26,17 +28,27 @@ impl Painter {
     /// Moves future paint operations to the specified location.
     pub fn move_to(&mut self, location: Vec2) -> &mut Self {
         let global_translation = self.translation + location;
+        self.location = global_translation;
         self.commands.push(DrawCommand::Translation(global_translation));
         self
     }
 
     /// Sets the color.
     pub fn set_color(&mut self, color: Color) -> &mut Self {
-        self.color = color;
+        self.brush = Brush::Solid(color);
         self.commands.push(DrawCommand::Color(color));
         self
     }
 
+    /// Sets the brush.
+    /// Gradient coordinates are relative to the current location.
+    pub fn set_brush(&mut self, brush: impl Into<Brush>) -> &mut Self {
+        let brush = brush.into().translated(self.location);
+        self.brush = brush.clone();
+        self.commands.push(DrawCommand::Brush(brush));
+        self
+    }
+
     /// Paints a circle with a radius.
     /// The number of points scales with the radius.
     pub fn paint_circle(&mut self, radius: f32) -> &mut Self {
@@ -56,35 +68,77 @@ impl Painter {
         self
     }
 
+    /// Paints a border along the inside edges of a rectangle with rounded corners.
+    /// Inner corners are rounded to follow the outer corners.
+    pub fn paint_border(&mut self, size: Vec2, corners: RawCorners, widths: RawSides, colors: BorderColor) -> &mut Self {
+        self.commands.push(DrawCommand::Border { size, corners, widths, colors });
+        self
+    }
+
+    /// Paints glyphs, relative to the current location.
+    pub fn paint_glyphs(&mut self, run: GlyphRun) -> &mut Self {
+        self.commands.push(DrawCommand::Glyphs(run));
+        self
+    }
+
+    /// Paints the shadow of a rectangle with rounded corners.
+    /// Outset shadows extend beyond the rectangle, so they should only be painted by the core.
+    /// Shadows that are not [visible](Shadow::is_visible) are skipped.
+    pub fn paint_shadow(&mut self, size: Vec2, corners: RawCorners, shadow: Shadow) -> &mut Self {
+        if !shadow.is_visible() { return self }
+        self.commands.push(DrawCommand::Shadow { size, corners, shadow });
+        self
+    }
+
     pub(crate) fn set_translation(&mut self, translation: Vec2) -> &mut Self {
         self.translation = translation;
+        self.location = translation;
         self.commands.push(DrawCommand::Translation(translation));
         self
     }
 
+    pub(crate) fn push_layer(&mut self) {
+        self.commands.push(DrawCommand::PushLayer);
+    }
+
+    pub(crate) fn pop_layer(&mut self, opacity: f32) {
+        self.commands.push(DrawCommand::PopLayer { opacity });
+    }
+
     pub(crate) fn resize(&mut self, size: Vec2, translation: Vec2, scale: f32) {
         self.commands.push(DrawCommand::Resize { size, translation, scale })
     }
 
-    pub(crate) fn push(&mut self) -> (Color, Vec2) {
-        (self.color, self.translation)
+    pub(crate) fn push(&mut self) -> (Brush, Vec2) {
+        (self.brush.clone(), self.translation)
     }
 
-    pub(crate) fn pop(&mut self, state: (Color, Vec2)) {
-        self.color = state.0;
+    pub(crate) fn pop(&mut self, state: (Brush, Vec2)) {
+        self.brush = state.0;
         self.translation = state.1;
-        self.commands.push(DrawCommand::Color(self.color));
+        self.location = state.1;
         self.commands.push(DrawCommand::Translation(self.translation));
+        self.commands.push(DrawCommand::Brush(self.brush.clone()));
     }
 }
 
 /// Drawing primitive to be implemented via a rendering backend.
-#[derive(Copy, Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub enum DrawCommand {
     Translation(Vec2),
     Color(Color),
+    /// Brush with gradient coordinates in the same space as [`DrawCommand::Translation`].
+    Brush(Brush),
     Circle { radius: f32 },
     Rect { size: Vec2 },
     RoundedRect { size: Vec2, top_left: f32, top_right: f32, bottom_right: f32, bottom_left: f32 },
+    Border { size: Vec2, corners: RawCorners, widths: RawSides, colors: BorderColor },
+    Shadow { size: Vec2, corners: RawCorners, shadow: Shadow },
+    /// Glyphs filled with the current color or brush.
+    Glyphs(GlyphRun),
+    /// Paints future shapes to a new layer, isolated from what is beneath it.
+    PushLayer,
+    /// Blends the current layer onto the one beneath it.
+    PopLayer { opacity: f32 },
     Resize { size: Vec2, translation: Vec2, scale: f32 }
 }
\ No newline at end of file
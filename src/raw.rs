@@ -3,6 +3,7 @@ use crate::Vec2;
 use crate::Rect;
 
 pub type RawMargin = RawSides;
+pub type RawBorder = RawSides;
 pub type RawPadding = RawSides;
 
 /// Stores raw values computed during the layout phase.
@@ -10,6 +11,7 @@ pub type RawPadding = RawSides;
 pub(crate) struct Raw {
     pub region: Rect,
     pub margin: RawMargin,
+    pub border: RawBorder,
     pub padding: RawPadding,
     pub corners: RawCorners,
     pub(crate) min_size: Vec2,
@@ -18,8 +20,8 @@ pub(crate) struct Raw {
 
 impl Raw {
 
-    // Region of the node containing the content + padding.
-    pub fn padding_region(&self) -> Rect {
+    // Region of the node containing the content + padding + border.
+    pub fn border_region(&self) -> Rect {
         let margin = &self.margin;
         let top_left = Vec2::new(margin.left, margin.top);
         let bottom_right = Vec2::new(margin.right, margin.bottom);
@@ -28,20 +30,19 @@ impl Raw {
         Rect { position, size }
     }
 
-    // Size of the padding region.
-    pub fn padding_region_size(&self) -> Vec2 {
+    // Size of the border region.
+    pub fn border_region_size(&self) -> Vec2 {
         let margin = &self.margin;
         let top_left = Vec2::new(margin.left, margin.top);
         let bottom_right = Vec2::new(margin.right, margin.bottom);
-        let size = self.region.size - top_left - bottom_right;
-        size
+        self.region.size - top_left - bottom_right
     }
 
     // Region of the node containing only the content.
     pub fn content_region(&self) -> Rect {
-        let (margin, padding) = (&self.margin, &self.padding);
-        let top_left = Vec2::new(margin.left, margin.top) + Vec2::new(padding.left, padding.top);
-        let bottom_right = Vec2::new(margin.right, margin.bottom) + Vec2::new(padding.right, padding.bottom);
+        let (margin, border, padding) = (&self.margin, &self.border, &self.padding);
+        let top_left = Vec2::new(margin.left, margin.top) + Vec2::new(border.left, border.top) + Vec2::new(padding.left, padding.top);
+        let bottom_right = Vec2::new(margin.right, margin.bottom) + Vec2::new(border.right, border.bottom) + Vec2::new(padding.right, padding.bottom);
         let position = self.region.position + top_left;
         let size = self.region.size - top_left - bottom_right;
         Rect { position, size }
@@ -49,32 +50,51 @@ impl Raw {
 
     // Width of the content region.
     pub fn width(&self) -> f32 {
-        self.region.size.x - (self.margin.left + self.margin.right + self.padding.left + self.padding.right)
+        self.region.size.x - self.frame_width()
     }
 
     // Height of the content region.
     pub fn height(&self) -> f32 {
-        self.region.size.y - (self.margin.bottom + self.margin.top + self.padding.bottom + self.padding.top)
+        self.region.size.y - self.frame_height()
     }
 
     // Sets the width of the region.
     pub fn set_width(&mut self, width: f32) {
-        self.region.size.x = width + (self.margin.left + self.margin.right + self.padding.left + self.padding.right);
+        self.region.size.x = width + self.frame_width();
     }
 
     // Sets the height of the region.
     pub fn set_height(&mut self, height: f32) {
-        self.region.size.y = height + (self.margin.bottom + self.margin.top + self.padding.bottom + self.padding.top);
+        self.region.size.y = height + self.frame_height();
     }
 
     pub fn set_size(&mut self, size: Vec2) {
-        self.region.size.x = size.x + (self.margin.left + self.margin.right + self.padding.left + self.padding.right);
-        self.region.size.y = size.y + (self.margin.top + self.margin.bottom + self.padding.top + self.padding.bottom);
+        self.region.size.x = size.x + self.frame_width();
+        self.region.size.y = size.y + self.frame_height();
     }
 
     pub fn full_width(&self) -> f32 {
         self.region.size.x
     }
+
+    // Space taken up by the margin, border and padding.
+    pub fn frame_size(&self) -> Vec2 {
+        Vec2::new(self.frame_width(), self.frame_height())
+    }
+
+    // Horizontal space taken up by the margin, border and padding.
+    fn frame_width(&self) -> f32 {
+        self.margin.left + self.margin.right +
+        self.border.left + self.border.right +
+        self.padding.left + self.padding.right
+    }
+
+    // Vertical space taken up by the margin, border and padding.
+    fn frame_height(&self) -> f32 {
+        self.margin.top + self.margin.bottom +
+        self.border.top + self.border.bottom +
+        self.padding.top + self.padding.bottom
+    }
 }
 
 /// Raw variant of [`crate::Corners`]
@@ -116,6 +136,15 @@ impl RawSides {
         (self.top(), self.right(), self.bottom(), self.left())
     }
 
+    pub(crate) fn round(self, unit: f32) -> Self {
+        Self {
+            top: (self.top / unit).round() * unit,
+            right: (self.right / unit).round() * unit,
+            bottom: (self.bottom / unit).round() * unit,
+            left: (self.left / unit).round() * unit
+        }
+    }
+
     pub fn rotate_right(self, rotate: bool) -> Self {
         if rotate {
             return Self {
@@ -0,0 +1,76 @@
+use std::fmt;
+use crate::Vec2;
+
+/// Condition on the viewport that determines if a [`crate::StyleRule`] is active.
+/// All bounds are inclusive, and unset bounds always match.
+///
+/// Written as "@media (min-width: 600px) and (max-height: 400px) { ... }" in a [`crate::StyleSheet`].
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct MediaQuery {
+    pub min_width: Option<f32>,
+    pub max_width: Option<f32>,
+    pub min_height: Option<f32>,
+    pub max_height: Option<f32>
+}
+
+impl MediaQuery {
+
+    pub fn with_min_width(mut self, min_width: f32) -> Self {
+        self.min_width = Some(min_width);
+        self
+    }
+
+    pub fn with_max_width(mut self, max_width: f32) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    pub fn with_min_height(mut self, min_height: f32) -> Self {
+        self.min_height = Some(min_height);
+        self
+    }
+
+    pub fn with_max_height(mut self, max_height: f32) -> Self {
+        self.max_height = Some(max_height);
+        self
+    }
+
+    /// True if the viewport size is within the bounds of the query.
+    pub fn matches(&self, viewport_size: Vec2) -> bool {
+        self.min_width.is_none_or(|min| viewport_size.x >= min) &&
+        self.max_width.is_none_or(|max| viewport_size.x <= max) &&
+        self.min_height.is_none_or(|min| viewport_size.y >= min) &&
+        self.max_height.is_none_or(|max| viewport_size.y <= max)
+    }
+
+    fn features(&self) -> [(&'static str, Option<f32>); 4] {
+        [
+            ("min-width", self.min_width),
+            ("max-width", self.max_width),
+            ("min-height", self.min_height),
+            ("max-height", self.max_height)
+        ]
+    }
+
+    pub(crate) fn feature_mut(&mut self, name: &str) -> Option<&mut Option<f32>> {
+        match name {
+            "min-width" => Some(&mut self.min_width),
+            "max-width" => Some(&mut self.max_width),
+            "min-height" => Some(&mut self.min_height),
+            "max-height" => Some(&mut self.max_height),
+            _ => None
+        }
+    }
+}
+
+impl fmt::Display for MediaQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "@media")?;
+        let features = self.features().into_iter().filter_map(|(name, px)| Some((name, px?)));
+        for (i, (name, px)) in features.enumerate() {
+            if i != 0 { write!(f, " and")? }
+            write!(f, " ({name}: {px}px)")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,606 @@
+mod parse;
+mod watch;
+mod media;
+
+pub use watch::*;
+pub use media::*;
+
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use crate::{Vec2, Style, Val, Sides, Corners, Border, BorderColor, Shadow, Color, Direction, Justify, Align, AlignSelf, GewyError, Result};
+
+/// Styles loaded from text, grouped into named rules.
+///
+/// Uses a CSS-like syntax, where each rule is a class name followed by declarations:
+/// ```text
+/// /* Comments are supported */
+/// card {
+///     width: 128px;
+///     height: 50%;
+///     color: #336699;
+///     margin: 4px 8px;
+///     border: 2px darkgray;
+///     corners: 10px;
+///     shadow: 4px 6px 8px rgba(0, 0, 0, 0.5);
+///     direction: column;
+/// }
+///
+/// /* Rules that only apply while the viewport matches */
+/// @media (max-width: 600px) {
+///     card { width: 100%; }
+/// }
+/// ```
+/// Rules are applied to nodes whose class names match the rule's selector.
+/// See [`crate::Node::with_class_name`] and [`crate::Gewy::set_style_sheet`].
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct StyleSheet {
+    pub rules: Vec<StyleRule>
+}
+
+impl StyleSheet {
+
+    /// Parses a style sheet from text.
+    /// Errors report the line and column where parsing failed.
+    pub fn parse(source: &str) -> Result<Self> {
+        parse::parse(source)
+    }
+
+    /// Reads and parses a style sheet file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path)
+            .map_err(|err| GewyError::Io(format!("{}: {}", path.display(), err)))?;
+        Self::parse(&source)
+    }
+
+    /// Applies all rules matching the class name, in order.
+    /// Rules with a [`MediaQuery`] are skipped.
+    pub fn apply(&self, class_name: &str, style: &mut Style) {
+        for rule in self.rules.iter().filter(|rule| rule.selector == class_name && rule.media.is_none()) {
+            rule.apply(style);
+        }
+    }
+
+    /// Applies all rules matching the class name without a [`MediaQuery`], in order.
+    /// Records each declaration applied in applied, latest last.
+    pub(crate) fn apply_base(&self, class_name: &str, style: &mut Style, applied: &mut Vec<Applied>) {
+        let rules = self.rules.iter().filter(|rule| rule.selector == class_name && rule.media.is_none());
+        Self::apply_recorded(rules, style, applied);
+    }
+
+    /// Applies all rules matching the class name whose [`MediaQuery`] matches the viewport, in order.
+    /// Records each declaration applied in applied, latest last.
+    pub(crate) fn apply_media(&self, class_name: &str, viewport_size: Vec2, style: &mut Style, applied: &mut Vec<Applied>) {
+        let rules = self.rules.iter().filter(|rule| {
+            rule.selector == class_name && rule.media.is_some_and(|media| media.matches(viewport_size))
+        });
+        Self::apply_recorded(rules, style, applied);
+    }
+
+    fn apply_recorded<'a>(rules: impl Iterator<Item = &'a StyleRule>, style: &mut Style, applied: &mut Vec<Applied>) {
+        for rule in rules {
+            for declaration in &rule.declarations {
+                applied.push(Applied { declaration: declaration.clone(), previous: declaration.capture(style) });
+                declaration.apply(style);
+            }
+        }
+    }
+
+    /// Undoes the declarations applied, latest first.
+    /// Properties changed since they were applied keep their new values.
+    pub(crate) fn unapply(applied: &mut Vec<Applied>, style: &mut Style) {
+        for Applied { declaration, previous } in applied.drain(..).rev() {
+            if declaration.capture(style) == declaration {
+                previous.apply(style);
+            }
+        }
+    }
+
+    /// Which media queries match the viewport, in rule order.
+    pub(crate) fn matching_media(&self, viewport_size: Vec2) -> Vec<bool> {
+        self.rules.iter()
+            .filter_map(|rule| rule.media)
+            .map(|media| media.matches(viewport_size))
+            .collect()
+    }
+}
+
+impl FromStr for StyleSheet {
+    type Err = GewyError;
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for StyleSheet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, rule) in self.rules.iter().enumerate() {
+            if i != 0 { writeln!(f)? }
+            write!(f, "{rule}")?;
+        }
+        Ok(())
+    }
+}
+
+// Declaration a style sheet applied to a style, and the value of the property before.
+#[derive(Clone, PartialEq, Debug)]
+pub(crate) struct Applied {
+    declaration: Declaration,
+    previous: Declaration
+}
+
+/// Declarations applied to nodes with a particular class name.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct StyleRule {
+    pub selector: String,
+    pub declarations: Vec<Declaration>,
+    /// Only applies while the viewport matches, if present.
+    pub media: Option<MediaQuery>
+}
+
+impl StyleRule {
+    pub fn new(selector: impl Into<String>, declarations: impl Into<Vec<Declaration>>) -> Self {
+        Self { selector: selector.into(), declarations: declarations.into(), media: None }
+    }
+
+    pub fn with_media(mut self, media: MediaQuery) -> Self {
+        self.media = Some(media);
+        self
+    }
+
+    pub fn apply(&self, style: &mut Style) {
+        for declaration in &self.declarations {
+            declaration.apply(style);
+        }
+    }
+}
+
+impl fmt::Display for StyleRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let indent = if self.media.is_some() { "    " } else { "" };
+        if let Some(media) = self.media {
+            writeln!(f, "{media} {{")?;
+        }
+        writeln!(f, "{indent}{} {{", self.selector)?;
+        for declaration in &self.declarations {
+            writeln!(f, "{indent}    {declaration};")?;
+        }
+        writeln!(f, "{indent}}}")?;
+        if self.media.is_some() {
+            writeln!(f, "}}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A single property of a [`Style`] and its value.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Declaration {
+    Width(Val),
+    Height(Val),
+    MinWidth(Val),
+    MinHeight(Val),
+    MaxWidth(Val),
+    MaxHeight(Val),
+    Basis(Val),
+    Color(Color),
+    Margin(Sides),
+    MarginSide(Side, Val),
+    Padding(Sides),
+    PaddingSide(Side, Val),
+    Border(Border),
+    BorderWidth(Sides),
+    BorderColor(BorderColor),
+    Corners(Corners),
+    Shadow(Option<Shadow>),
+    Opacity(f32),
+    Direction(Direction),
+    Justify(Justify),
+    Align(Align),
+    AlignSelf(AlignSelf),
+    Grow(f32),
+    Shrink(f32),
+    Focusable(bool),
+    TabIndex(i32)
+}
+
+impl Declaration {
+    pub fn apply(&self, style: &mut Style) {
+        match *self {
+            Self::Width(val) => style.width = val,
+            Self::Height(val) => style.height = val,
+            Self::MinWidth(val) => style.min_width = val,
+            Self::MinHeight(val) => style.min_height = val,
+            Self::MaxWidth(val) => style.max_width = val,
+            Self::MaxHeight(val) => style.max_height = val,
+            Self::Basis(val) => style.basis = val,
+            Self::Color(color) => style.color = color,
+            Self::Margin(sides) => style.margin = sides,
+            Self::MarginSide(side, val) => *side.of(&mut style.margin) = val,
+            Self::Padding(sides) => style.padding = sides,
+            Self::PaddingSide(side, val) => *side.of(&mut style.padding) = val,
+            Self::Border(border) => style.border = border,
+            Self::BorderWidth(sides) => style.border.width = sides,
+            Self::BorderColor(color) => style.border.color = color,
+            Self::Corners(corners) => style.corners = corners,
+            Self::Shadow(shadow) => style.shadow = shadow,
+            Self::Opacity(opacity) => style.opacity = opacity,
+            Self::Direction(direction) => style.direction = direction,
+            Self::Justify(justify) => style.justify = justify,
+            Self::Align(align) => style.align = align,
+            Self::AlignSelf(align_self) => style.align_self = align_self,
+            Self::Grow(grow) => style.grow = grow,
+            Self::Shrink(shrink) => style.shrink = shrink,
+            Self::Focusable(focusable) => style.focusable = focusable,
+            Self::TabIndex(tab_index) => style.tab_index = tab_index
+        }
+    }
+
+    /// Same property, with the value it currently has in the style.
+    pub fn capture(&self, style: &Style) -> Self {
+        match *self {
+            Self::Width(_) => Self::Width(style.width),
+            Self::Height(_) => Self::Height(style.height),
+            Self::MinWidth(_) => Self::MinWidth(style.min_width),
+            Self::MinHeight(_) => Self::MinHeight(style.min_height),
+            Self::MaxWidth(_) => Self::MaxWidth(style.max_width),
+            Self::MaxHeight(_) => Self::MaxHeight(style.max_height),
+            Self::Basis(_) => Self::Basis(style.basis),
+            Self::Color(_) => Self::Color(style.color),
+            Self::Margin(_) => Self::Margin(style.margin),
+            Self::MarginSide(side, _) => Self::MarginSide(side, side.get(&style.margin)),
+            Self::Padding(_) => Self::Padding(style.padding),
+            Self::PaddingSide(side, _) => Self::PaddingSide(side, side.get(&style.padding)),
+            Self::Border(_) => Self::Border(style.border),
+            Self::BorderWidth(_) => Self::BorderWidth(style.border.width),
+            Self::BorderColor(_) => Self::BorderColor(style.border.color),
+            Self::Corners(_) => Self::Corners(style.corners),
+            Self::Shadow(_) => Self::Shadow(style.shadow),
+            Self::Opacity(_) => Self::Opacity(style.opacity),
+            Self::Direction(_) => Self::Direction(style.direction),
+            Self::Justify(_) => Self::Justify(style.justify),
+            Self::Align(_) => Self::Align(style.align),
+            Self::AlignSelf(_) => Self::AlignSelf(style.align_self),
+            Self::Grow(_) => Self::Grow(style.grow),
+            Self::Shrink(_) => Self::Shrink(style.shrink),
+            Self::Focusable(_) => Self::Focusable(style.focusable),
+            Self::TabIndex(_) => Self::TabIndex(style.tab_index)
+        }
+    }
+}
+
+impl fmt::Display for Declaration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Width(val) => write!(f, "width: {}", Fmt(val)),
+            Self::Height(val) => write!(f, "height: {}", Fmt(val)),
+            Self::MinWidth(val) => write!(f, "min-width: {}", Fmt(val)),
+            Self::MinHeight(val) => write!(f, "min-height: {}", Fmt(val)),
+            Self::MaxWidth(val) => write!(f, "max-width: {}", Fmt(val)),
+            Self::MaxHeight(val) => write!(f, "max-height: {}", Fmt(val)),
+            Self::Basis(val) => write!(f, "basis: {}", Fmt(val)),
+            Self::Color(color) => write!(f, "color: {}", Fmt(color)),
+            Self::Margin(sides) => write!(f, "margin: {}", Fmt(sides)),
+            Self::MarginSide(side, val) => write!(f, "margin-{}: {}", side.keyword(), Fmt(val)),
+            Self::Padding(sides) => write!(f, "padding: {}", Fmt(sides)),
+            Self::PaddingSide(side, val) => write!(f, "padding-{}: {}", side.keyword(), Fmt(val)),
+            Self::Border(border) => match uniform_border(border) {
+                Some((width, color)) => write!(f, "border: {} {}", Fmt(&width), Fmt(&color)),
+                // The shorthand only sets every side alike, so other borders take both longhands.
+                None => write!(f, "border-width: {}; border-color: {}", Fmt(&border.width), Fmt(&border.color))
+            },
+            Self::BorderWidth(sides) => write!(f, "border-width: {}", Fmt(sides)),
+            Self::BorderColor(color) => write!(f, "border-color: {}", Fmt(color)),
+            Self::Corners(corners) => write!(f, "corners: {}", Fmt(corners)),
+            Self::Shadow(None) => write!(f, "shadow: none"),
+            Self::Shadow(Some(shadow)) => write!(f, "shadow: {}", Fmt(shadow)),
+            Self::Opacity(opacity) => write!(f, "opacity: {opacity}"),
+            Self::Direction(direction) => write!(f, "direction: {}", keyword(DIRECTIONS, *direction)),
+            Self::Justify(justify) => write!(f, "justify: {}", keyword(JUSTIFIES, *justify)),
+            Self::Align(align) => write!(f, "align: {}", keyword(ALIGNS, *align)),
+            Self::AlignSelf(align_self) => write!(f, "align-self: {}", keyword(ALIGN_SELVES, *align_self)),
+            Self::Grow(grow) => write!(f, "grow: {grow}"),
+            Self::Shrink(shrink) => write!(f, "shrink: {shrink}"),
+            Self::Focusable(focusable) => write!(f, "focusable: {}", keyword(BOOLEANS, *focusable)),
+            Self::TabIndex(tab_index) => write!(f, "tab-index: {tab_index}")
+        }
+    }
+}
+
+/// One side of [`Sides`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Side { Top, Right, Bottom, Left }
+
+impl Side {
+    fn get(self, sides: &Sides) -> Val {
+        match self {
+            Self::Top => sides.top,
+            Self::Right => sides.right,
+            Self::Bottom => sides.bottom,
+            Self::Left => sides.left
+        }
+    }
+    fn of(self, sides: &mut Sides) -> &mut Val {
+        match self {
+            Self::Top => &mut sides.top,
+            Self::Right => &mut sides.right,
+            Self::Bottom => &mut sides.bottom,
+            Self::Left => &mut sides.left
+        }
+    }
+    fn keyword(self) -> &'static str {
+        keyword(SIDES, self)
+    }
+}
+
+// Keywords of enum values, shared by the parser and the formatter.
+pub(crate) const SIDES: &[(&str, Side)] = &[
+    ("top", Side::Top),
+    ("right", Side::Right),
+    ("bottom", Side::Bottom),
+    ("left", Side::Left)
+];
+pub(crate) const DIRECTIONS: &[(&str, Direction)] = &[
+    ("row", Direction::Row),
+    ("row-reverse", Direction::RowReverse),
+    ("column", Direction::Column),
+    ("column-reverse", Direction::ColumnReverse)
+];
+pub(crate) const JUSTIFIES: &[(&str, Justify)] = &[
+    ("start", Justify::Start),
+    ("end", Justify::End),
+    ("center", Justify::Center),
+    ("space-between", Justify::SpaceBetween),
+    ("space-around", Justify::SpaceAround),
+    ("space-evenly", Justify::SpaceEvenly)
+];
+pub(crate) const ALIGNS: &[(&str, Align)] = &[
+    ("center", Align::Center),
+    ("stretch", Align::Stretch),
+    ("start", Align::Start),
+    ("end", Align::End)
+];
+pub(crate) const BOOLEANS: &[(&str, bool)] = &[
+    ("true", true),
+    ("false", false)
+];
+pub(crate) const ALIGN_SELVES: &[(&str, AlignSelf)] = &[
+    ("auto", AlignSelf::Auto),
+    ("stretch", AlignSelf::Stretch),
+    ("center", AlignSelf::Center),
+    ("start", AlignSelf::Start),
+    ("end", AlignSelf::End)
+];
+
+fn keyword<T: PartialEq>(keywords: &[(&'static str, T)], value: T) -> &'static str {
+    keywords.iter()
+        .find(|(_, v)| *v == value)
+        .map(|(keyword, _)| *keyword)
+        .unwrap()
+}
+
+// Width and color of a border whose sides are all alike, which the border shorthand can express.
+fn uniform_border(border: &Border) -> Option<(Val, Color)> {
+    let Sides { top: width, .. } = border.width;
+    let BorderColor { top: color, .. } = border.color;
+    (border.width == Sides::all(width) && border.color == BorderColor::all(color)).then_some((width, color))
+}
+
+// Formats values in the syntax the parser accepts.
+struct Fmt<'a, T>(&'a T);
+
+impl fmt::Display for Fmt<'_, Val> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Val::Px(px) => write!(f, "{px}px"),
+            Val::Pc(pc) => write!(f, "{}%", pc * 100.0),
+            Val::Auto => write!(f, "auto")
+        }
+    }
+}
+
+impl fmt::Display for Fmt<'_, Color> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.to_hex())
+    }
+}
+
+impl fmt::Display for Fmt<'_, Sides> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Sides { top, right, bottom, left } = self.0;
+        write!(f, "{} {} {} {}", Fmt(top), Fmt(right), Fmt(bottom), Fmt(left))
+    }
+}
+
+impl fmt::Display for Fmt<'_, BorderColor> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let BorderColor { top, right, bottom, left } = self.0;
+        write!(f, "{} {} {} {}", Fmt(top), Fmt(right), Fmt(bottom), Fmt(left))
+    }
+}
+
+impl fmt::Display for Fmt<'_, Corners> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Corners { top_left, top_right, bottom_right, bottom_left } = self.0;
+        write!(f, "{} {} {} {}", Fmt(top_left), Fmt(top_right), Fmt(bottom_right), Fmt(bottom_left))
+    }
+}
+
+impl fmt::Display for Fmt<'_, Shadow> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let shadow = self.0;
+        write!(
+            f, "{}px {}px {}px {}px {}",
+            shadow.offset.x, shadow.offset.y, shadow.blur, shadow.spread, Fmt(&shadow.color)
+        )?;
+        if shadow.inset { write!(f, " inset")? }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{StyleSheet, StyleRule, Declaration, Side, Style, Val, Sides, Corners, Border, BorderColor, Color, Shadow, Direction, Justify, GewyError, Vec2, Gewy, Node, MediaQuery};
+
+    #[test]
+    fn test_parse() {
+        let sheet = StyleSheet::parse("
+            /* Card */
+            card {
+                width: 128px;
+                height: 50%;
+                color: rgb(255, 0, 0);
+                margin: 4px 8px;
+                padding-left: 2px;
+                border: 2px black;
+                corners: 10px;
+                shadow: 4px 6px 8px #00000080 inset;
+                direction: column-reverse;
+                justify: space-between
+            }
+            // Empty
+            .empty {}
+        ").unwrap();
+        assert_eq!(
+            StyleSheet {
+                rules: vec![
+                    StyleRule::new("card", [
+                        Declaration::Width(Val::Px(128.0)),
+                        Declaration::Height(Val::Pc(0.5)),
+                        Declaration::Color(Color::RED),
+                        Declaration::Margin(Sides::new(Val::Px(4.0), Val::Px(8.0), Val::Px(4.0), Val::Px(8.0))),
+                        Declaration::PaddingSide(Side::Left, Val::Px(2.0)),
+                        Declaration::Border(Border::all(Val::Px(2.0), Color::BLACK)),
+                        Declaration::Corners(Corners::all(Val::Px(10.0))),
+                        Declaration::Shadow(Some(Shadow::inset(Vec2::new(4.0, 6.0), 8.0, 0.0, Color::from_rgba8(0, 0, 0, 128)))),
+                        Declaration::Direction(Direction::ColumnReverse),
+                        Declaration::Justify(Justify::SpaceBetween)
+                    ]),
+                    StyleRule::new("empty", [])
+                ]
+            },
+            sheet
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |source: &str| match StyleSheet::parse(source) {
+            Err(GewyError::InvalidStyleSheet { line, column, .. }) => (line, column),
+            other => panic!("Expected error, got {other:?}")
+        };
+        assert_eq!((2, 5), error("card {\n    colour: red;\n}"));
+        assert_eq!((2, 12), error("card {\n    width: 12pz;\n}"));
+        assert_eq!((1, 1), error("card {\n    width: 12px;\n"));
+        assert_eq!((3, 1), error("card {}\n\n{}"));
+        assert_eq!((1, 16), error("card { margin: 1px 2px 3px 4px 5px; }"));
+        assert_eq!((1, 9), error("@media (max-widht: 1px) {}"));
+        assert_eq!((1, 20), error("@media (max-width: 1em) {}"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut style = Style::default();
+        let sheet = StyleSheet::parse("
+            a {
+                width: 25%;
+                max-height: auto;
+                color: hsl(210, 50%, 40%);
+                border-width: 1px 2px 3px 4px;
+                border-color: red green blue white;
+                corners: 1px 2px;
+                shadow: none;
+                opacity: 0.5;
+                align-self: end;
+                grow: 2;
+                focusable: true;
+                tab-index: -1;
+            }
+        ").unwrap();
+        sheet.apply("a", &mut style);
+        let reparsed = StyleSheet::parse(&sheet.to_string()).unwrap();
+        let mut reparsed_style = Style::default();
+        reparsed.apply("a", &mut reparsed_style);
+        assert_eq!(style, reparsed_style);
+        assert_eq!(Val::Pc(0.25), style.width);
+        assert!(style.focusable);
+        assert_eq!(-1, style.tab_index);
+        assert_eq!(Corners::new(Val::Px(1.0), Val::Px(2.0), Val::Px(1.0), Val::Px(2.0)), style.corners);
+
+        // The border shorthand stays a single declaration.
+        let sheet = StyleSheet::parse("a { border: 2px #ff0000; } b { border: 1px #00ff00; border-width: 3px; }").unwrap();
+        assert_eq!("a {\n    border: 2px #ff0000ff;\n}\n", sheet.rules[0].to_string());
+        assert_eq!(sheet, StyleSheet::parse(&sheet.to_string()).unwrap());
+        let uneven = Border::new(Sides::top(Val::Px(1.0)), BorderColor::all(Color::RED));
+        let declaration = Declaration::Border(uneven);
+        let sheet = StyleSheet::parse(&format!("a {{ {declaration}; }}")).unwrap();
+        sheet.apply("a", &mut style);
+        assert_eq!(uneven, style.border);
+    }
+
+    #[test]
+    fn test_apply_to_gewy() {
+        let mut gewy = Gewy::new(Node::default());
+        let root_id = gewy.root_id();
+        let child_id = gewy.insert(root_id, Node::default().with_class_name("a")).unwrap();
+        gewy.resize(Vec2::new(200.0, 200.0));
+
+        gewy.set_style_sheet(StyleSheet::parse("a { width: 10px; height: 20px; }").unwrap());
+        assert_eq!(Vec2::new(10.0, 20.0), gewy.get(child_id).unwrap().raw.region.size);
+
+        // Nodes inserted later get the current style sheet too.
+        let other_id = gewy.insert(root_id, Node::default().with_class_name("a")).unwrap();
+        assert_eq!(Val::Px(10.0), gewy.get(other_id).unwrap().style().width);
+
+        // Removed declarations revert to the value from before the style sheet.
+        gewy.set_style_sheet(StyleSheet::parse("a { width: 30px; }").unwrap());
+        let style = gewy.get(other_id).unwrap().style();
+        assert_eq!(Val::Px(30.0), style.width);
+        assert_eq!(Val::default(), style.height);
+        gewy.set_style_sheet(StyleSheet::default());
+        assert_eq!(Style::default(), *gewy.get(child_id).unwrap().style());
+
+        // Properties edited since the previous style sheet keep the edits.
+        gewy.set_style_sheet(StyleSheet::parse("a { width: 10px; height: 20px; }").unwrap());
+        gewy.get_mut(child_id).unwrap().style.width = Val::Px(40.0);
+        gewy.set_style_sheet(StyleSheet::parse("a { height: 30px; }").unwrap());
+        let style = gewy.get(child_id).unwrap().style();
+        assert_eq!((Val::Px(40.0), Val::Px(30.0)), (style.width, style.height));
+    }
+
+    #[test]
+    fn test_media() {
+        let sheet = StyleSheet::parse("
+            sidebar { width: 200px; }
+            @media (max-width: 600px) and (min-height: 100px) {
+                sidebar { width: 0px; }
+            }
+        ").unwrap();
+        assert_eq!(
+            Some(MediaQuery::default().with_max_width(600.0).with_min_height(100.0)),
+            sheet.rules[1].media
+        );
+        assert_eq!(sheet, StyleSheet::parse(&sheet.to_string()).unwrap());
+
+        let mut gewy = Gewy::new(Node::default()).with_style_sheet(sheet);
+        let root_id = gewy.root_id();
+        let sidebar_id = gewy.insert(root_id, Node::default().with_class_name("sidebar")).unwrap();
+        let width = |gewy: &Gewy| gewy.get(sidebar_id).unwrap().style().width;
+        gewy.resize(Vec2::new(800.0, 400.0));
+        assert_eq!(Val::Px(200.0), width(&gewy));
+        gewy.resize(Vec2::new(500.0, 400.0));
+        assert_eq!(Val::Px(0.0), width(&gewy));
+        gewy.resize(Vec2::new(500.0, 50.0));
+        assert_eq!(Val::Px(200.0), width(&gewy));
+
+        // Styles edited since a breakpoint was crossed keep the edits.
+        gewy.resize(Vec2::new(500.0, 400.0));
+        let style = &mut gewy.get_mut(sidebar_id).unwrap().style;
+        style.width = Val::Px(120.0);
+        style.grow = 1.0;
+        gewy.resize(Vec2::new(800.0, 400.0));
+        let style = gewy.get(sidebar_id).unwrap().style();
+        assert_eq!((Val::Px(120.0), 1.0), (style.width, style.grow));
+    }
+}
@@ -0,0 +1,418 @@
+use crate::{Val, Sides, Corners, Border, BorderColor, Shadow, Color, GewyError, Result, Vec2};
+use crate::sheet::{StyleSheet, StyleRule, Declaration, MediaQuery, SIDES, DIRECTIONS, JUSTIFIES, ALIGNS, ALIGN_SELVES, BOOLEANS};
+
+pub(crate) fn parse(source: &str) -> Result<StyleSheet> {
+    let mut parser = Parser { chars: source.chars().peekable(), line: 1, column: 1 };
+    let mut rules = Vec::new();
+    loop {
+        parser.skip_trivia()?;
+        match parser.peek() {
+            Some('@') => parser.media(&mut rules)?,
+            Some(_) => rules.push(parser.rule()?),
+            None => break
+        }
+    }
+    Ok(StyleSheet { rules })
+}
+
+// Line and column, starting at 1.
+type Position = (usize, usize);
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+    column: usize
+}
+
+impl<'a> Parser<'a> {
+
+    // Parses "selector { declaration; ... }"
+    fn rule(&mut self) -> Result<StyleRule> {
+        let start = self.position();
+        if self.peek() == Some('.') { self.bump(); }
+        let selector = self.identifier();
+        if selector.is_empty() {
+            return Err(self.unexpected(start, "a class name"));
+        }
+        self.skip_trivia()?;
+        self.expect('{')?;
+        let mut declarations = Vec::new();
+        loop {
+            self.skip_trivia()?;
+            match self.peek() {
+                Some('}') => { self.bump(); break }
+                Some(_) => declarations.push(self.declaration()?),
+                None => return Err(error(start, format!("Rule '{selector}' is missing a closing '}}'")))
+            }
+        }
+        Ok(StyleRule::new(selector, declarations))
+    }
+
+    // Parses "@media (feature: value) and ... { rule ... }"
+    fn media(&mut self, rules: &mut Vec<StyleRule>) -> Result<()> {
+        let start = self.position();
+        self.bump();
+        let keyword = self.identifier();
+        if keyword != "media" {
+            return Err(error(start, format!("Unknown at-rule '@{keyword}', expected '@media'")));
+        }
+
+        // Conditions
+        let mut media = MediaQuery::default();
+        loop {
+            self.skip_trivia()?;
+            let position = self.position();
+            match self.peek() {
+                Some('{') => { self.bump(); break }
+                Some('(') => {
+                    self.bump();
+                    self.skip_trivia()?;
+                    let name_start = self.position();
+                    let name = self.identifier();
+                    let Some(feature) = media.feature_mut(&name) else {
+                        return Err(error(name_start, format!("Unknown media feature '{name}'")));
+                    };
+                    self.skip_trivia()?;
+                    self.expect(':')?;
+                    self.skip_trivia()?;
+                    let value_start = self.position();
+                    let mut value = String::new();
+                    while let Some(c) = self.peek().filter(|c| *c != ')' && *c != '{') {
+                        value.push(c);
+                        self.bump();
+                    }
+                    *feature = Some(parse_px(value.trim()).map_err(|message| error(value_start, message))?);
+                    self.expect(')')?;
+                }
+                _ => match self.identifier().as_str() {
+                    "and" => {}
+                    "" => return Err(self.unexpected(position, "'(', 'and' or '{'")),
+                    word => return Err(error(position, format!("Expected '(', 'and' or '{{', found '{word}'")))
+                }
+            }
+        }
+
+        // Rules
+        loop {
+            self.skip_trivia()?;
+            match self.peek() {
+                Some('}') => { self.bump(); break }
+                Some(_) => rules.push(self.rule()?.with_media(media)),
+                None => return Err(error(start, "Media rule is missing a closing '}'".to_owned()))
+            }
+        }
+        Ok(())
+    }
+
+    // Parses "property: value;", where the semicolon is optional before a "}".
+    fn declaration(&mut self) -> Result<Declaration> {
+        let start = self.position();
+        let property = self.identifier();
+        if property.is_empty() {
+            return Err(self.unexpected(start, "a property name"));
+        }
+        if !PROPERTIES.contains(&property.as_str()) {
+            return Err(error(start, format!("Unknown property '{property}'")));
+        }
+        self.skip_trivia()?;
+        self.expect(':')?;
+        self.skip_trivia()?;
+        let value_start = self.position();
+        let value = self.value();
+        if value.is_empty() {
+            return Err(error(value_start, format!("Missing value for '{property}'")));
+        }
+        if self.peek() == Some(';') { self.bump(); }
+        parse_declaration(&property, &value).map_err(|message| error(value_start, message))
+    }
+
+    // Reads up until a ";" or "}" that is not between parentheses.
+    fn value(&mut self) -> String {
+        let mut value = String::new();
+        let mut depth = 0;
+        while let Some(c) = self.peek() {
+            match c {
+                ';' | '}' if depth == 0 => break,
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => {}
+            }
+            value.push(c);
+            self.bump();
+        }
+        value.trim_end().to_owned()
+    }
+
+    fn identifier(&mut self) -> String {
+        let mut identifier = String::new();
+        while let Some(c) = self.peek().filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_') {
+            identifier.push(c);
+            self.bump();
+        }
+        identifier
+    }
+
+    fn expect(&mut self, expected: char) -> Result<()> {
+        let position = self.position();
+        if self.peek() == Some(expected) {
+            self.bump();
+            Ok(())
+        }
+        else {
+            Err(self.unexpected(position, &format!("'{expected}'")))
+        }
+    }
+
+    // Skips whitespace and comments.
+    fn skip_trivia(&mut self) -> Result<()> {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() => { self.bump(); }
+                Some('/') => {
+                    let start = self.position();
+                    self.bump();
+                    match self.bump() {
+                        Some('/') => while self.peek().is_some_and(|c| c != '\n') { self.bump(); },
+                        Some('*') => loop {
+                            match self.bump() {
+                                Some('*') if self.peek() == Some('/') => { self.bump(); break }
+                                Some(_) => {}
+                                None => return Err(error(start, "Comment is missing a closing '*/'".to_owned()))
+                            }
+                        },
+                        _ => return Err(error(start, "Expected a comment after '/'".to_owned()))
+                    }
+                }
+                _ => return Ok(())
+            }
+        }
+    }
+
+    fn unexpected(&mut self, position: Position, expected: &str) -> GewyError {
+        match self.peek() {
+            Some(c) => error(position, format!("Expected {expected}, found '{c}'")),
+            None => error(position, format!("Expected {expected}, found end of file"))
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        }
+        else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn position(&self) -> Position {
+        (self.line, self.column)
+    }
+}
+
+fn error((line, column): Position, message: String) -> GewyError {
+    GewyError::InvalidStyleSheet { line, column, message }
+}
+
+const PROPERTIES: &[&str] = &[
+    "width", "height", "min-width", "min-height", "max-width", "max-height", "basis", "color",
+    "margin", "margin-top", "margin-right", "margin-bottom", "margin-left",
+    "padding", "padding-top", "padding-right", "padding-bottom", "padding-left",
+    "border", "border-width", "border-color", "corners", "shadow", "opacity",
+    "direction", "justify", "align", "align-self", "grow", "shrink", "focusable", "tab-index"
+];
+
+// Parses the value of a known property.
+fn parse_declaration(property: &str, value: &str) -> std::result::Result<Declaration, String> {
+    let values = split(value);
+    let declaration = match property {
+        "width" => Declaration::Width(single(&values, parse_val)?),
+        "height" => Declaration::Height(single(&values, parse_val)?),
+        "min-width" => Declaration::MinWidth(single(&values, parse_val)?),
+        "min-height" => Declaration::MinHeight(single(&values, parse_val)?),
+        "max-width" => Declaration::MaxWidth(single(&values, parse_val)?),
+        "max-height" => Declaration::MaxHeight(single(&values, parse_val)?),
+        "basis" => Declaration::Basis(single(&values, parse_val)?),
+        "color" => Declaration::Color(single(&values, parse_color)?),
+        "margin" => Declaration::Margin(parse_sides(&values)?),
+        "padding" => Declaration::Padding(parse_sides(&values)?),
+        "border-width" => Declaration::BorderWidth(parse_sides(&values)?),
+        "border-color" => {
+            let [top, right, bottom, left] = expand(&values, parse_color)?;
+            Declaration::BorderColor(BorderColor::new(top, right, bottom, left))
+        },
+        "border" => match values[..] {
+            [width, color] => Declaration::Border(Border::all(parse_val(width)?, parse_color(color)?)),
+            _ => return Err(format!("Expected a width and a color, found {} values", values.len()))
+        },
+        "corners" => {
+            let [top_left, top_right, bottom_right, bottom_left] = expand(&values, parse_val)?;
+            Declaration::Corners(Corners::new(top_left, top_right, bottom_right, bottom_left))
+        },
+        "shadow" => Declaration::Shadow(parse_shadow(&values)?),
+        "opacity" => Declaration::Opacity(single(&values, parse_fraction)?),
+        "direction" => Declaration::Direction(single(&values, |v| parse_keyword(v, DIRECTIONS))?),
+        "justify" => Declaration::Justify(single(&values, |v| parse_keyword(v, JUSTIFIES))?),
+        "align" => Declaration::Align(single(&values, |v| parse_keyword(v, ALIGNS))?),
+        "align-self" => Declaration::AlignSelf(single(&values, |v| parse_keyword(v, ALIGN_SELVES))?),
+        "grow" => Declaration::Grow(single(&values, parse_number)?),
+        "shrink" => Declaration::Shrink(single(&values, parse_number)?),
+        "focusable" => Declaration::Focusable(single(&values, |v| parse_keyword(v, BOOLEANS))?),
+        "tab-index" => Declaration::TabIndex(single(&values, parse_integer)?),
+        _ => {
+            let (property, side) = property.rsplit_once('-').unwrap();
+            let side = parse_keyword(side, SIDES)?;
+            let val = single(&values, parse_val)?;
+            match property {
+                "margin" => Declaration::MarginSide(side, val),
+                _ => Declaration::PaddingSide(side, val)
+            }
+        }
+    };
+    Ok(declaration)
+}
+
+// Splits a value on whitespace that is not between parentheses.
+fn split(value: &str) -> Vec<&str> {
+    let mut values = Vec::new();
+    let mut depth = 0;
+    let mut start = None;
+    for (i, c) in value.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            c if c.is_whitespace() && depth == 0 => {
+                if let Some(start) = start.take() {
+                    values.push(&value[start..i]);
+                }
+                continue
+            }
+            _ => {}
+        }
+        start.get_or_insert(i);
+    }
+    if let Some(start) = start {
+        values.push(&value[start..]);
+    }
+    values
+}
+
+fn single<T>(values: &[&str], parse: impl Fn(&str) -> std::result::Result<T, String>) -> std::result::Result<T, String> {
+    match values {
+        [value] => parse(value),
+        _ => Err(format!("Expected a single value, found {}", values.len()))
+    }
+}
+
+// Expands 1-4 values into top, right, bottom, left, the same way CSS does.
+fn expand<T: Copy>(values: &[&str], parse: impl Fn(&str) -> std::result::Result<T, String>) -> std::result::Result<[T; 4], String> {
+    let values = values.iter().map(|value| parse(value)).collect::<std::result::Result<Vec<T>, String>>()?;
+    match values[..] {
+        [all] => Ok([all, all, all, all]),
+        [vertical, horizontal] => Ok([vertical, horizontal, vertical, horizontal]),
+        [top, horizontal, bottom] => Ok([top, horizontal, bottom, horizontal]),
+        [top, right, bottom, left] => Ok([top, right, bottom, left]),
+        _ => Err(format!("Expected 1 to 4 values, found {}", values.len()))
+    }
+}
+
+fn parse_sides(values: &[&str]) -> std::result::Result<Sides, String> {
+    let [top, right, bottom, left] = expand(values, parse_val)?;
+    Ok(Sides::new(top, right, bottom, left))
+}
+
+fn parse_val(value: &str) -> std::result::Result<Val, String> {
+    let invalid = || format!("Invalid value '{value}', expected a length such as '10px', '50%' or 'auto'");
+    if value == "auto" {
+        Ok(Val::Auto)
+    }
+    else if let Some(px) = value.strip_suffix("px") {
+        parse_number(px).map(Val::Px).map_err(|_| invalid())
+    }
+    else if let Some(pc) = value.strip_suffix('%') {
+        parse_number(pc).map(|pc| Val::Pc(pc / 100.0)).map_err(|_| invalid())
+    }
+    else if value == "0" {
+        Ok(Val::Px(0.0))
+    }
+    else {
+        Err(invalid())
+    }
+}
+
+fn parse_px(value: &str) -> std::result::Result<f32, String> {
+    match parse_val(value) {
+        Ok(Val::Px(px)) => Ok(px),
+        _ => Err(format!("Invalid value '{value}', expected pixels such as '10px'"))
+    }
+}
+
+fn parse_number(value: &str) -> std::result::Result<f32, String> {
+    value.parse::<f32>().ok()
+        .filter(|number| number.is_finite())
+        .ok_or_else(|| format!("Invalid number '{value}'"))
+}
+
+fn parse_integer(value: &str) -> std::result::Result<i32, String> {
+    value.parse::<i32>().map_err(|_| format!("Invalid integer '{value}'"))
+}
+
+// Number, or percentage of 1.0.
+fn parse_fraction(value: &str) -> std::result::Result<f32, String> {
+    match value.strip_suffix('%') {
+        Some(pc) => parse_number(pc).map(|pc| pc / 100.0),
+        None => parse_number(value)
+    }
+}
+
+fn parse_color(value: &str) -> std::result::Result<Color, String> {
+    Color::parse(value).map_err(|err| err.to_string())
+}
+
+fn parse_keyword<T: Copy>(value: &str, keywords: &[(&str, T)]) -> std::result::Result<T, String> {
+    keywords.iter()
+        .find(|(keyword, _)| *keyword == value)
+        .map(|(_, value)| *value)
+        .ok_or_else(|| {
+            let expected: Vec<&str> = keywords.iter().map(|(keyword, _)| *keyword).collect();
+            format!("Invalid value '{value}', expected one of: {}", expected.join(", "))
+        })
+}
+
+// Parses "none" or "[inset] x y [blur [spread]] color".
+fn parse_shadow(values: &[&str]) -> std::result::Result<Option<Shadow>, String> {
+    if values == ["none"] {
+        return Ok(None);
+    }
+    let mut inset = false;
+    let mut lengths = Vec::new();
+    let mut color = None;
+    for value in values {
+        if *value == "inset" {
+            inset = true;
+        }
+        else if value.ends_with("px") || *value == "0" {
+            lengths.push(parse_px(value)?);
+        }
+        else if color.is_none() {
+            color = Some(parse_color(value)?);
+        }
+        else {
+            return Err(format!("Unexpected value '{value}' in shadow"));
+        }
+    }
+    let (offset, blur, spread) = match lengths[..] {
+        [x, y] => (Vec2::new(x, y), 0.0, 0.0),
+        [x, y, blur] => (Vec2::new(x, y), blur, 0.0),
+        [x, y, blur, spread] => (Vec2::new(x, y), blur, spread),
+        _ => return Err(format!("Expected 2 to 4 lengths in shadow, found {}", lengths.len()))
+    };
+    let color = color.ok_or_else(|| "Shadow is missing a color".to_owned())?;
+    Ok(Some(Shadow { offset, blur, spread, color, inset }))
+}
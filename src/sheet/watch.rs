@@ -0,0 +1,64 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+use crate::{Gewy, StyleSheet, Result};
+
+/// Reloads a [`StyleSheet`] file whenever it changes.
+/// Changes are detected by polling the file's modification time, at most once per interval.
+pub struct StyleSheetWatcher {
+    path: PathBuf,
+    pub interval: Duration,
+    last_poll: Option<Instant>,
+    modified: Option<SystemTime>,
+    loaded: bool
+}
+
+impl StyleSheetWatcher {
+
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            interval: Duration::from_millis(250),
+            last_poll: None,
+            modified: None,
+            loaded: false
+        }
+    }
+
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn path(&self) -> &Path { &self.path }
+
+    /// Loads the style sheet if it is the first poll, or if the file changed since the last poll.
+    /// Each failure is only reported once, until the file changes again.
+    pub fn poll(&mut self) -> Result<Option<StyleSheet>> {
+        let now = Instant::now();
+        if self.last_poll.is_some_and(|last_poll| now - last_poll < self.interval) {
+            return Ok(None);
+        }
+        self.last_poll = Some(now);
+        let modified = std::fs::metadata(&self.path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        if self.loaded && modified == self.modified {
+            return Ok(None);
+        }
+        self.loaded = true;
+        self.modified = modified;
+        StyleSheet::load(&self.path).map(Some)
+    }
+
+    /// Polls the file, and sets the style sheet of the [`Gewy`] if it was reloaded.
+    /// Returns true if the style sheet was reloaded.
+    pub fn update(&mut self, gewy: &mut Gewy) -> Result<bool> {
+        match self.poll()? {
+            Some(sheet) => {
+                gewy.set_style_sheet(sheet);
+                Ok(true)
+            },
+            None => Ok(false)
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::Vec2;
 
-use crate::{Color, RawCorners, RawSides, RawMargin, RawPadding};
+use crate::{Color, Brush, RawCorners, RawSides, RawMargin, RawBorder, RawPadding};
 
 pub type Margin = Sides;
 pub type Padding = Sides;
@@ -13,20 +13,40 @@ pub struct Style {
     pub min_height: Val,
     pub max_width: Val,
     pub max_height: Val,
+    /// Color of text, and of the background unless there is a brush.
     pub color: Color,
+    /// Fills the background instead of the color if present.
+    /// Gradient coordinates are relative to the size of the node (0.0 - 1.0).
+    pub brush: Option<Brush>,
     pub margin: Sides,
+    pub border: Border,
     pub padding: Sides,
     pub corners: Corners,
+    pub shadow: Option<Shadow>,
+    /// Opacity (0.0 - 1.0) of the node and its descendants, blended together as a group.
+    pub opacity: f32,
     pub direction: Direction,
     pub justify: Justify,
     pub align: Align,
     pub grow: f32,
     pub shrink: f32,
     pub basis: Val,
-    pub align_self: AlignSelf
+    pub align_self: AlignSelf,
+    /// If true, the node takes keyboard focus when pressed, or when reached with tab.
+    pub focusable: bool,
+    /// Order of a focusable node when traversed with tab.
+    /// Positive indices come first in ascending order, followed by zero in tree order.
+    /// Negative indices are skipped.
+    pub tab_index: i32
 }
 
 impl Style {
+
+    /// Brush that fills the background: the brush if present, otherwise the color.
+    pub fn fill(&self) -> Brush {
+        self.brush.clone().unwrap_or(Brush::Solid(self.color))
+    }
+
     pub(crate) fn raw_width(&self, parent_width: f32, is_row: bool) -> f32 {
         let width = if is_row { self.width } else { self.height };
         match width {
@@ -65,6 +85,10 @@ impl Style {
         Self::raw_sides(&self.margin, parent_size, is_row)
     }
 
+    pub(crate) fn raw_border(&self, parent_size: Vec2, is_row: bool) -> RawBorder {
+        Self::raw_sides(&self.border.width, parent_size, is_row)
+    }
+
     pub(crate) fn raw_padding(&self, parent_size: Vec2, is_row: bool) -> RawPadding {
         Self::raw_sides(&self.padding, parent_size, is_row)
     }
@@ -109,16 +133,22 @@ impl Default for Style {
             max_width: Val::default(),
             max_height: Val::default(),
             color: Default::default(),
+            brush: None,
             margin: Default::default(),
+            border: Default::default(),
             padding: Default::default(),
             corners: Default::default(),
+            shadow: None,
+            opacity: 1.0,
             justify: Default::default(),
             direction: Default::default(),
             align: Default::default(),
             grow: 0.0,
             shrink: 1.0,
             basis: Val::default(),
-            align_self: AlignSelf::default()
+            align_self: AlignSelf::default(),
+            focusable: false,
+            tab_index: 0
         }      
     }
 }
@@ -192,6 +222,85 @@ impl Sides {
     }
 }
 
+/// Border surrounding the padding of a node.
+/// Takes up space between the margin and the padding.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct Border {
+    pub width: Sides,
+    pub color: BorderColor
+}
+
+impl Border {
+    pub fn new(width: Sides, color: BorderColor) -> Self {
+        Self { width, color }
+    }
+    pub fn all(width: Val, color: Color) -> Self {
+        Self::new(Sides::all(width), BorderColor::all(color))
+    }
+}
+
+/// Colors of each side of a [`Border`].
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct BorderColor {
+    pub top: Color,
+    pub right: Color,
+    pub bottom: Color,
+    pub left: Color
+}
+
+impl BorderColor {
+    pub fn new(top: Color, right: Color, bottom: Color, left: Color) -> Self {
+        Self { top, right, bottom, left }
+    }
+    pub fn all(all: Color) -> Self {
+        Self::new(all, all, all, all)
+    }
+}
+
+/// Outline painted around the focused node, once it has been focused with the keyboard.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct FocusRing {
+    pub color: Color,
+    pub width: f32,
+    /// Gap between the border of the node and the ring.
+    pub offset: f32
+}
+
+impl Default for FocusRing {
+    fn default() -> Self {
+        Self { color: Color::LIGHT_BLUE, width: 2.0, offset: 1.0 }
+    }
+}
+
+/// Shadow cast by a node.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct Shadow {
+    /// Offset of the shadow in pixels.
+    pub offset: Vec2,
+    /// Distance in pixels over which the edge of the shadow fades out.
+    pub blur: f32,
+    /// Distance in pixels to grow the shadow by before blurring.
+    pub spread: f32,
+    pub color: Color,
+    /// If true, the shadow is painted inside of the node rather than beneath it.
+    pub inset: bool
+}
+
+impl Shadow {
+    pub fn new(offset: Vec2, blur: f32, spread: f32, color: Color) -> Self {
+        Self { offset, blur, spread, color, inset: false }
+    }
+    pub fn inset(offset: Vec2, blur: f32, spread: f32, color: Color) -> Self {
+        Self { offset, blur, spread, color, inset: true }
+    }
+
+    /// False if the shadow would paint nothing, because it is transparent or lies exactly under (or along) the edges of its rectangle.
+    pub fn is_visible(&self) -> bool {
+        let hidden = self.offset == Vec2::ZERO && self.blur <= 0.0 && self.spread <= 0.0;
+        self.color.a() > 0.0 && !hidden
+    }
+}
+
 /// Corner radiuses
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Corners {
@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+use crate::{Font, Vec2, UVec2};
+
+/// Single channel texture that rasterized glyphs are packed into, in rows called shelves.
+/// Backends upload the texture, and draw glyphs as quads sampling their region of it.
+///
+/// A small block in the top left corner is always fully covered, so that untextured shapes can sample it.
+/// Once the atlas is full, shelves with no glyphs used in the current frame are evicted to make room.
+pub struct GlyphAtlas {
+    size: u32,
+    max_size: u32,
+    data: Vec<u8>,
+    glyphs: HashMap<GlyphKey, Option<AtlasGlyph>>,
+    shelves: Vec<Shelf>,
+    frame: u64,
+    dirty: bool
+}
+
+impl GlyphAtlas {
+
+    /// Texel coordinates that are always fully covered.
+    pub const SOLID: Vec2 = Vec2::new(2.0, 2.0);
+
+    // Size of the fully covered block.
+    const SOLID_SIZE: u32 = 4;
+
+    // Empty space between glyphs, so that filtering does not bleed into neighbors.
+    const PADDING: u32 = 1;
+
+    // Fractions of a pixel glyphs can be horizontally offset by.
+    const SUBPIXEL_STEPS: f32 = 4.0;
+
+    /// Creates an atlas of a particular size, that may grow up to a maximum size.
+    pub fn new(size: u32, max_size: u32) -> Self {
+        let mut slf = Self {
+            size,
+            max_size: max_size.max(size),
+            data: vec![0; (size * size) as usize],
+            glyphs: HashMap::new(),
+            shelves: Vec::new(),
+            frame: 0,
+            dirty: true
+        };
+        for y in 0..Self::SOLID_SIZE {
+            let start = (y * size) as usize;
+            slf.data[start..start + Self::SOLID_SIZE as usize].fill(255);
+        }
+        slf
+    }
+
+    /// Width and height of the texture.
+    pub fn size(&self) -> u32 { self.size }
+
+    /// Row-major coverage values of the texture.
+    pub fn data(&self) -> &[u8] { &self.data }
+
+    /// True if the texture changed since the last call.
+    pub fn take_dirty(&mut self) -> bool {
+        std::mem::take(&mut self.dirty)
+    }
+
+    /// Starts a new frame.
+    /// Glyphs not used since can be evicted when the atlas is full.
+    pub fn end_frame(&mut self) {
+        self.frame += 1;
+    }
+
+    /// Gets a glyph, rasterizing it into the atlas if it is not already present.
+    /// The subpixel offset is rounded to a quarter of a pixel.
+    /// Returns None if the glyph has no outline, or the atlas is full of glyphs used in the current frame.
+    pub fn glyph(&mut self, font: &Font, glyph_id: u16, size: f32, subpixel_offset: f32) -> Option<AtlasGlyph> {
+        let subpixel = (subpixel_offset.fract() * Self::SUBPIXEL_STEPS).round() as u8;
+        let key = GlyphKey {
+            font_id: font.id(),
+            glyph_id,
+            size: size.to_bits(),
+            subpixel
+        };
+        if let Some(glyph) = self.glyphs.get(&key).copied() {
+            if let Some(glyph) = glyph {
+                self.touch(glyph.position.y);
+            }
+            return glyph;
+        }
+        let Some(bitmap) = font.rasterize(glyph_id, size, subpixel as f32 / Self::SUBPIXEL_STEPS) else {
+            self.glyphs.insert(key, None);
+            return None;
+        };
+
+        // Glyphs that do not fit are not cached, so they are tried again once space is freed.
+        let position = self.allocate(UVec2::new(bitmap.width, bitmap.height))?;
+        for y in 0..bitmap.height {
+            let src = (y * bitmap.width) as usize;
+            let dst = ((position.y + y) * self.size + position.x) as usize;
+            self.data[dst..dst + bitmap.width as usize]
+                .copy_from_slice(&bitmap.coverage[src..src + bitmap.width as usize]);
+        }
+        self.dirty = true;
+        let glyph = AtlasGlyph {
+            position,
+            size: UVec2::new(bitmap.width, bitmap.height),
+            offset: bitmap.offset
+        };
+        self.glyphs.insert(key, Some(glyph));
+        Some(glyph)
+    }
+
+    // Marks the shelf starting at a row as used in the current frame.
+    fn touch(&mut self, y: u32) {
+        let frame = self.frame;
+        if let Some(shelf) = self.shelves.iter_mut().find(|shelf| shelf.y == y) {
+            shelf.frame = frame;
+        }
+    }
+
+    // Finds space for a region, growing the atlas if needed.
+    fn allocate(&mut self, size: UVec2) -> Option<UVec2> {
+        let padded = size + Self::PADDING;
+        loop {
+
+            // Uses the first shelf that is tall enough without wasting too much space.
+            let atlas_size = self.size;
+            let shelf = self.shelves.iter_mut().find(|shelf| {
+                shelf.height >= padded.y &&
+                shelf.height <= padded.y * 3 / 2 + 1 &&
+                shelf.x + padded.x <= atlas_size
+            });
+            if let Some(shelf) = shelf {
+                let position = UVec2::new(shelf.x, shelf.y);
+                shelf.x += padded.x;
+                shelf.frame = self.frame;
+                return Some(position);
+            }
+
+            // Otherwise, opens a new shelf below the others.
+            let y = self.shelves.last()
+                .map(|shelf| shelf.y + shelf.height)
+                .unwrap_or(Self::SOLID_SIZE + Self::PADDING);
+            if y + padded.y <= self.size && padded.x <= self.size {
+                self.shelves.push(Shelf { y, height: padded.y, x: padded.x, frame: self.frame });
+                return Some(UVec2::new(0, y));
+            }
+            if !self.grow() && !self.evict() {
+                return None;
+            }
+        }
+    }
+
+    // Doubles the size of the texture, keeping existing glyphs where they are.
+    fn grow(&mut self) -> bool {
+        let size = self.size * 2;
+        if size > self.max_size {
+            return false;
+        }
+        let mut data = vec![0; (size * size) as usize];
+        for y in 0..self.size {
+            let src = (y * self.size) as usize;
+            let dst = (y * size) as usize;
+            data[dst..dst + self.size as usize].copy_from_slice(&self.data[src..src + self.size as usize]);
+        }
+        self.size = size;
+        self.data = data;
+        self.dirty = true;
+        true
+    }
+
+    // Empties shelves with no glyphs used in the current frame, so glyphs being painted keep their place.
+    // Empty shelves at the bottom are removed, so their rows can be reopened with any height.
+    fn evict(&mut self) -> bool {
+        let frame = self.frame;
+        let stale: Vec<Shelf> = self.shelves.iter()
+            .filter(|shelf| shelf.x > 0 && shelf.frame != frame)
+            .copied()
+            .collect();
+        if stale.is_empty() {
+            return false;
+        }
+        self.glyphs.retain(|_, glyph| match glyph {
+            Some(glyph) => stale.iter().all(|shelf| shelf.y != glyph.position.y),
+            None => true
+        });
+        for shelf in &stale {
+            let start = (shelf.y * self.size) as usize;
+            let end = ((shelf.y + shelf.height) * self.size) as usize;
+            self.data[start..end].fill(0);
+        }
+        for shelf in &mut self.shelves {
+            if shelf.frame != frame {
+                shelf.x = 0;
+            }
+        }
+        while self.shelves.last().is_some_and(|shelf| shelf.x == 0) {
+            self.shelves.pop();
+        }
+        self.dirty = true;
+        true
+    }
+}
+
+/// Region of a glyph in a [`GlyphAtlas`].
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct AtlasGlyph {
+    /// Texel coordinates of the top left corner.
+    pub position: UVec2,
+    pub size: UVec2,
+    /// Position of the top left corner relative to the glyph's origin on the baseline.
+    pub offset: Vec2
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+struct GlyphKey {
+    font_id: u64,
+    glyph_id: u16,
+    size: u32,
+    subpixel: u8
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Shelf {
+    y: u32,
+    height: u32,
+    x: u32,
+    // Last frame a glyph on the shelf was used in.
+    frame: u64
+}
@@ -0,0 +1,356 @@
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+
+// Maximum number of edits that can be undone.
+const UNDO_LIMIT: usize = 100;
+
+/// Editable text with a caret, a selection and an undo history.
+///
+/// Offsets are in bytes, and always fall between grapheme clusters when moved by the editor.
+/// The selection spans from the anchor to the caret, and is empty when they are equal.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct TextEditor {
+    text: String,
+    caret: usize,
+    anchor: usize,
+    multi_line: bool,
+    undo_stack: Vec<Snapshot>,
+    redo_stack: Vec<Snapshot>,
+    last_edit: Option<EditKind>
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+struct Snapshot {
+    text: String,
+    caret: usize,
+    anchor: usize
+}
+
+// Kind of edit, where consecutive edits of the same kind are undone together.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum EditKind { Insert, Delete, Replace }
+
+impl TextEditor {
+
+    /// Editor of a single line of text, with the caret at its end.
+    pub fn new(text: impl Into<String>) -> Self {
+        let mut slf = Self::default();
+        slf.set_text(text);
+        slf
+    }
+
+    /// Allows line breaks to be inserted.
+    pub fn with_multi_line(mut self, multi_line: bool) -> Self {
+        self.multi_line = multi_line;
+        let text = std::mem::take(&mut self.text);
+        self.set_text(text);
+        self
+    }
+
+    pub fn text(&self) -> &str { &self.text }
+    pub fn caret(&self) -> usize { self.caret }
+    pub fn anchor(&self) -> usize { self.anchor }
+    pub fn multi_line(&self) -> bool { self.multi_line }
+
+    /// Byte range of the selected text.
+    pub fn selection(&self) -> Range<usize> {
+        self.caret.min(self.anchor)..self.caret.max(self.anchor)
+    }
+
+    pub fn has_selection(&self) -> bool {
+        self.caret != self.anchor
+    }
+
+    pub fn selected_text(&self) -> &str {
+        &self.text[self.selection()]
+    }
+
+    /// Replaces the text, moving the caret to its end and clearing the undo history.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = self.filter(&text.into());
+        self.caret = self.text.len();
+        self.anchor = self.caret;
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.last_edit = None;
+    }
+
+    /// Moves the caret to an offset, which is clamped to the text and moved back to a character boundary.
+    /// If extending, the anchor stays where it is, growing or shrinking the selection.
+    pub fn set_caret(&mut self, byte: usize, extend: bool) {
+        let mut byte = byte.min(self.text.len());
+        while !self.text.is_char_boundary(byte) {
+            byte -= 1;
+        }
+        self.caret = byte;
+        if !extend {
+            self.anchor = byte;
+        }
+        self.last_edit = None;
+    }
+
+    pub fn select_all(&mut self) {
+        self.anchor = 0;
+        self.caret = self.text.len();
+        self.last_edit = None;
+    }
+
+    /// Moves the caret to the previous grapheme, or word start.
+    /// Without extending, a selection collapses to its start instead.
+    pub fn move_left(&mut self, word: bool, extend: bool) {
+        let byte = if self.has_selection() && !extend && !word {
+            self.selection().start
+        }
+        else if word {
+            self.prev_word(self.caret)
+        }
+        else {
+            self.prev_grapheme(self.caret)
+        };
+        self.set_caret(byte, extend);
+    }
+
+    /// Moves the caret to the next grapheme, or word end.
+    /// Without extending, a selection collapses to its end instead.
+    pub fn move_right(&mut self, word: bool, extend: bool) {
+        let byte = if self.has_selection() && !extend && !word {
+            self.selection().end
+        }
+        else if word {
+            self.next_word(self.caret)
+        }
+        else {
+            self.next_grapheme(self.caret)
+        };
+        self.set_caret(byte, extend);
+    }
+
+    /// Inserts text at the caret, replacing the selection.
+    /// Line breaks are replaced with spaces unless the editor is multi-line.
+    /// Returns true if the text changed.
+    pub fn insert(&mut self, text: &str) -> bool {
+        let text = self.filter(text);
+        if text.is_empty() && !self.has_selection() { return false }
+
+        // Words are undone one at a time.
+        let kind = if self.has_selection() { EditKind::Replace } else { EditKind::Insert };
+        let starts_word = text.chars().next().is_some_and(|c| !c.is_whitespace()) &&
+            self.text[..self.caret].chars().next_back().is_some_and(char::is_whitespace);
+        self.record(kind, starts_word);
+
+        let selection = self.selection();
+        self.text.replace_range(selection.clone(), &text);
+        self.caret = selection.start + text.len();
+        self.anchor = self.caret;
+        self.last_edit = Some(EditKind::Insert);
+        true
+    }
+
+    /// Deletes the selection, or the grapheme or word before the caret.
+    /// Returns true if the text changed.
+    pub fn delete_backward(&mut self, word: bool) -> bool {
+        let range = if self.has_selection() {
+            self.selection()
+        }
+        else if word {
+            self.prev_word(self.caret)..self.caret
+        }
+        else {
+            self.prev_grapheme(self.caret)..self.caret
+        };
+        self.delete(range)
+    }
+
+    /// Deletes the selection, or the grapheme or word after the caret.
+    /// Returns true if the text changed.
+    pub fn delete_forward(&mut self, word: bool) -> bool {
+        let range = if self.has_selection() {
+            self.selection()
+        }
+        else if word {
+            self.caret..self.next_word(self.caret)
+        }
+        else {
+            self.caret..self.next_grapheme(self.caret)
+        };
+        self.delete(range)
+    }
+
+    /// Reverts the last edit, returning true if there was one.
+    pub fn undo(&mut self) -> bool {
+        let Some(snapshot) = self.undo_stack.pop() else { return false };
+        let current = self.restore(snapshot);
+        self.redo_stack.push(current);
+        true
+    }
+
+    /// Reapplies the last undone edit, returning true if there was one.
+    pub fn redo(&mut self) -> bool {
+        let Some(snapshot) = self.redo_stack.pop() else { return false };
+        let current = self.restore(snapshot);
+        self.undo_stack.push(current);
+        true
+    }
+
+    pub fn can_undo(&self) -> bool { !self.undo_stack.is_empty() }
+    pub fn can_redo(&self) -> bool { !self.redo_stack.is_empty() }
+
+    fn delete(&mut self, range: Range<usize>) -> bool {
+        if range.is_empty() { return false }
+        let kind = if self.has_selection() { EditKind::Replace } else { EditKind::Delete };
+        self.record(kind, false);
+        self.text.replace_range(range.clone(), "");
+        self.caret = range.start;
+        self.anchor = range.start;
+        self.last_edit = Some(EditKind::Delete);
+        true
+    }
+
+    // Saves the text before an edit, unless it continues the last edit.
+    fn record(&mut self, kind: EditKind, force: bool) {
+        self.redo_stack.clear();
+        if !force && kind != EditKind::Replace && self.last_edit == Some(kind) { return }
+        if self.undo_stack.len() == UNDO_LIMIT {
+            self.undo_stack.remove(0);
+        }
+        self.undo_stack.push(Snapshot { text: self.text.clone(), caret: self.caret, anchor: self.anchor });
+    }
+
+    // Restores a snapshot, returning the current state.
+    fn restore(&mut self, snapshot: Snapshot) -> Snapshot {
+        let current = Snapshot { text: std::mem::take(&mut self.text), caret: self.caret, anchor: self.anchor };
+        self.text = snapshot.text;
+        self.caret = snapshot.caret;
+        self.anchor = snapshot.anchor;
+        self.last_edit = None;
+        current
+    }
+
+    fn filter(&self, text: &str) -> String {
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        if self.multi_line { text } else { text.replace('\n', " ") }
+    }
+
+    fn prev_grapheme(&self, byte: usize) -> usize {
+        self.text[..byte].grapheme_indices(true).next_back().map_or(0, |(i, _)| i)
+    }
+
+    fn next_grapheme(&self, byte: usize) -> usize {
+        self.text[byte..].graphemes(true).next().map_or(byte, |grapheme| byte + grapheme.len())
+    }
+
+    // Start of the word before an offset, skipping whitespace and punctuation.
+    fn prev_word(&self, byte: usize) -> usize {
+        self.text[..byte]
+            .split_word_bound_indices()
+            .rev()
+            .find(|(_, word)| word.chars().any(char::is_alphanumeric))
+            .map_or(0, |(i, _)| i)
+    }
+
+    // End of the word after an offset, skipping whitespace and punctuation.
+    fn next_word(&self, byte: usize) -> usize {
+        self.text[byte..]
+            .split_word_bound_indices()
+            .find(|(_, word)| word.chars().any(char::is_alphanumeric))
+            .map_or(self.text.len(), |(i, word)| byte + i + word.len())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::TextEditor;
+
+    #[test]
+    fn test_edit() {
+        let mut editor = TextEditor::new("hello");
+        assert_eq!(5, editor.caret());
+
+        // Inserts at the caret, and replaces selections.
+        editor.insert(" world");
+        editor.set_caret(0, false);
+        editor.set_caret(5, true);
+        assert_eq!("hello", editor.selected_text());
+        editor.insert("goodbye");
+        assert_eq!("goodbye world", editor.text());
+        assert!(!editor.has_selection());
+
+        // Deletes whole graphemes and words.
+        let mut editor = TextEditor::new("one two e\u{301}");
+        editor.delete_backward(false);
+        assert_eq!("one two ", editor.text());
+        editor.delete_backward(true);
+        assert_eq!("one ", editor.text());
+        editor.set_caret(0, false);
+        editor.delete_forward(true);
+        assert_eq!(" ", editor.text());
+
+        // Single lines replace line breaks.
+        let mut editor = TextEditor::new("a\nb");
+        assert_eq!("a b", editor.text());
+        editor.insert("\r\nc");
+        assert_eq!("a b c", editor.text());
+        let mut editor = TextEditor::default().with_multi_line(true);
+        editor.set_text("a\nb");
+        editor.insert("\r\nc");
+        assert_eq!("a\nb\nc", editor.text());
+    }
+
+    #[test]
+    fn test_movement() {
+        let mut editor = TextEditor::new("ab, cd e\u{301}");
+        editor.move_left(false, false);
+        assert_eq!(7, editor.caret());
+        editor.move_left(true, false);
+        assert_eq!(4, editor.caret());
+        editor.move_left(true, true);
+        assert_eq!(0..4, editor.selection());
+        editor.move_right(true, true);
+        assert_eq!(2, editor.caret());
+        assert_eq!(2..4, editor.selection());
+
+        // Collapses selections to their side.
+        editor.move_right(false, false);
+        assert_eq!(4, editor.caret());
+        assert!(!editor.has_selection());
+        editor.select_all();
+        editor.move_left(false, false);
+        assert_eq!(0, editor.caret());
+
+        // Clamps carets to character boundaries.
+        editor.set_caret(9, false);
+        assert_eq!(8, editor.caret());
+    }
+
+    #[test]
+    fn test_undo() {
+        let mut editor = TextEditor::new("");
+
+        // Typing is undone a word at a time.
+        for c in ["a", "b", " ", "c", "d"] {
+            editor.insert(c);
+        }
+        editor.delete_backward(false);
+        assert_eq!("ab c", editor.text());
+        assert!(editor.undo());
+        assert_eq!("ab cd", editor.text());
+        assert!(editor.undo());
+        assert_eq!("ab ", editor.text());
+        assert!(editor.undo());
+        assert_eq!("", editor.text());
+        assert!(!editor.undo());
+
+        // Redoes until something new is edited.
+        assert!(editor.redo());
+        assert_eq!("ab ", editor.text());
+        assert_eq!(3, editor.caret());
+        editor.insert("x");
+        assert!(!editor.redo());
+
+        // Moving the caret ends the edit.
+        editor.move_left(false, false);
+        editor.insert("y");
+        editor.undo();
+        assert_eq!("ab x", editor.text());
+    }
+}
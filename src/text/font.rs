@@ -0,0 +1,139 @@
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use ab_glyph::{Font as _, FontVec, GlyphId, PxScale, point};
+use crate::{GewyError, Result, Vec2};
+
+/// A TrueType or OpenType font.
+/// Cheap to clone, as clones share the same font data.
+#[derive(Clone)]
+pub struct Font(Arc<FontData>);
+
+struct FontData {
+    id: u64,
+    font: FontVec
+}
+
+impl Font {
+
+    /// Loads a font from the bytes of a TTF or OTF file.
+    pub fn from_bytes(bytes: impl Into<Vec<u8>>) -> Result<Self> {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        let font = FontVec::try_from_vec(bytes.into()).map_err(|_| GewyError::InvalidFont)?;
+        rustybuzz::Face::from_slice(font.as_slice(), 0).ok_or(GewyError::InvalidFont)?;
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        Ok(Self(Arc::new(FontData { id, font })))
+    }
+
+    /// Unique id of the font, shared by its clones.
+    pub fn id(&self) -> u64 {
+        self.0.id
+    }
+
+    /// Vertical metrics at a font size in pixels.
+    pub fn metrics(&self, size: f32) -> FontMetrics {
+        let font = &self.0.font;
+        let scale = self.scale(size);
+        FontMetrics {
+            ascent: font.ascent_unscaled() * scale,
+            descent: -font.descent_unscaled() * scale,
+            line_gap: font.line_gap_unscaled() * scale
+        }
+    }
+
+    /// Id of the glyph for a character, or 0 if the font does not have one.
+    pub fn glyph_id(&self, c: char) -> u16 {
+        self.0.font.glyph_id(c).0
+    }
+
+    /// True if the font has a glyph for the character.
+    pub fn has_glyph(&self, c: char) -> bool {
+        self.glyph_id(c) != 0
+    }
+
+    /// Horizontal distance to the next glyph.
+    pub fn advance(&self, glyph_id: u16, size: f32) -> f32 {
+        self.0.font.h_advance_unscaled(GlyphId(glyph_id)) * self.scale(size)
+    }
+
+    /// Adjustment to the distance between two glyphs.
+    pub fn kerning(&self, first: u16, second: u16, size: f32) -> f32 {
+        self.0.font.kern_unscaled(GlyphId(first), GlyphId(second)) * self.scale(size)
+    }
+
+    /// Rasterizes a glyph whose origin is offset horizontally by a fraction of a pixel.
+    /// Returns None for glyphs without an outline, like spaces.
+    pub fn rasterize(&self, glyph_id: u16, size: f32, subpixel_offset: f32) -> Option<GlyphBitmap> {
+        let font = &self.0.font;
+        let px_scale = PxScale::from(size * font.height_unscaled() * self.scale(1.0));
+        let glyph = GlyphId(glyph_id).with_scale_and_position(px_scale, point(subpixel_offset, 0.0));
+        let outlined = font.outline_glyph(glyph)?;
+        let bounds = outlined.px_bounds();
+        let width = bounds.width() as u32;
+        let height = bounds.height() as u32;
+        if width == 0 || height == 0 { return None }
+        let mut coverage = vec![0; (width * height) as usize];
+        outlined.draw(|x, y, c| {
+            coverage[(y * width + x) as usize] = (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+        });
+        Some(GlyphBitmap {
+            offset: Vec2::new(bounds.min.x, bounds.min.y),
+            width,
+            height,
+            coverage
+        })
+    }
+
+    // Face used for shaping, borrowing the bytes of the font.
+    // Parsing only reads table offsets, so it is cheap enough to do per shaping call.
+    pub(crate) fn face(&self) -> rustybuzz::Face<'_> {
+        rustybuzz::Face::from_slice(self.0.font.as_slice(), 0).expect("Font was validated when loaded")
+    }
+
+    // Converts font units to pixels at a font size.
+    pub(crate) fn scale(&self, size: f32) -> f32 {
+        let units_per_em = self.0.font.units_per_em().unwrap_or(1000.0);
+        size / units_per_em
+    }
+}
+
+impl PartialEq for Font {
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+    }
+}
+
+impl fmt::Debug for Font {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Font").field("id", &self.id()).finish()
+    }
+}
+
+/// Vertical metrics of a [`Font`] at a particular size, in pixels.
+#[derive(Copy, Clone, PartialEq, Default, Debug)]
+pub struct FontMetrics {
+    /// Distance from the baseline to the top of the tallest glyphs.
+    pub ascent: f32,
+    /// Distance from the baseline to the bottom of the lowest glyphs.
+    pub descent: f32,
+    /// Extra space between lines.
+    pub line_gap: f32
+}
+
+impl FontMetrics {
+    /// Distance between the baselines of consecutive lines.
+    pub fn line_height(&self) -> f32 {
+        self.ascent + self.descent + self.line_gap
+    }
+}
+
+/// Coverage of a rasterized glyph.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GlyphBitmap {
+    /// Position of the top left pixel, relative to the glyph's origin on the baseline.
+    pub offset: Vec2,
+    pub width: u32,
+    pub height: u32,
+    /// Row-major coverage values, from 0 (empty) to 255 (covered).
+    pub coverage: Vec<u8>
+}
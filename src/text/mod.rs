@@ -0,0 +1,135 @@
+mod font;
+mod atlas;
+mod paragraph;
+mod shape;
+mod editor;
+
+pub use font::*;
+pub use atlas::*;
+pub use paragraph::*;
+pub use shape::*;
+pub use editor::*;
+
+use crate::Vec2;
+
+/// A glyph positioned within a [`GlyphRun`].
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct PositionedGlyph {
+    pub id: u16,
+    /// Position of the glyph's origin on the baseline.
+    pub position: Vec2
+}
+
+/// Glyphs of a single font and size, ready to be painted.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GlyphRun {
+    pub font: Font,
+    pub size: f32,
+    pub glyphs: Vec<PositionedGlyph>,
+    /// Size of the area the glyphs were laid out in.
+    pub bounds: Vec2
+}
+
+impl GlyphRun {
+
+    /// Lays out text like a [`Paragraph`] of a single span, without wrapping.
+    /// The top of the first line is at the origin.
+    pub fn new(text: &str, font: Font, size: f32) -> Self {
+        let layout = Paragraph::new(text, font.clone(), size).layout(f32::INFINITY);
+        let glyphs = layout.runs.into_iter().flat_map(|(run, _)| run.glyphs).collect();
+        Self { font, size, glyphs, bounds: layout.size }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use crate::{Font, GlyphRun, GlyphAtlas, Vec2, Gewy, Node, Text, Direction, Style};
+
+    pub(crate) fn font() -> Font {
+        Font::from_bytes(include_bytes!("../../assets/fonts/DejaVuSans.ttf").as_slice()).unwrap()
+    }
+
+    // Font without Arabic or Hebrew glyphs.
+    pub(crate) fn serif_font() -> Font {
+        Font::from_bytes(include_bytes!("../../assets/fonts/DejaVuSerif.ttf").as_slice()).unwrap()
+    }
+
+    #[test]
+    fn test_glyph_run() {
+        let font = font();
+        let run = GlyphRun::new("AV\nA", font.clone(), 20.0);
+        let metrics = font.metrics(20.0);
+        assert_eq!(3, run.glyphs.len());
+        assert_eq!(Vec2::new(0.0, metrics.ascent), run.glyphs[0].position);
+        assert_eq!(metrics.line_height() + metrics.ascent, run.glyphs[2].position.y);
+        assert_eq!(2.0 * metrics.line_height(), run.bounds.y);
+
+        // Kerning pulls "V" towards "A".
+        let advance = font.advance(font.glyph_id('A'), 20.0);
+        assert!(run.glyphs[1].position.x < advance);
+        assert_eq!(run.glyphs[1].position.x + font.advance(font.glyph_id('V'), 20.0), run.bounds.x);
+    }
+
+    #[test]
+    fn test_atlas() {
+        let font = font();
+        let mut atlas = GlyphAtlas::new(32, 64);
+        assert_eq!(255, atlas.data()[(GlyphAtlas::SOLID.y as u32 * atlas.size() + GlyphAtlas::SOLID.x as u32) as usize]);
+        assert!(atlas.glyph(&font, font.glyph_id(' '), 16.0, 0.0).is_none());
+
+        // Glyphs do not overlap, and the atlas grows to fit them.
+        let glyphs: Vec<_> = "abcdefgh".chars()
+            .map(|c| atlas.glyph(&font, font.glyph_id(c), 16.0, 0.0).unwrap())
+            .collect();
+        for (i, a) in glyphs.iter().enumerate() {
+            for b in &glyphs[i+1..] {
+                let overlaps_x = a.position.x < b.position.x + b.size.x && b.position.x < a.position.x + a.size.x;
+                let overlaps_y = a.position.y < b.position.y + b.size.y && b.position.y < a.position.y + a.size.y;
+                assert!(!(overlaps_x && overlaps_y));
+            }
+        }
+        assert_eq!(64, atlas.size());
+        assert_eq!(Some(glyphs[0]), atlas.glyph(&font, font.glyph_id('a'), 16.0, 0.0));
+
+        // Glyphs that do not fit are skipped.
+        assert!(atlas.glyph(&font, font.glyph_id('W'), 100.0, 0.0).is_none());
+    }
+
+    #[test]
+    fn test_atlas_eviction() {
+        let font = font();
+        let mut atlas = GlyphAtlas::new(64, 64);
+        let glyph = |atlas: &mut GlyphAtlas, c| atlas.glyph(&font, font.glyph_id(c), 20.0, 0.0);
+
+        // Fills the atlas within a single frame, where nothing can be evicted.
+        let a = glyph(&mut atlas, 'a').unwrap();
+        let full = "bcdefghijklmnopqrstuvwxyz".chars().find(|c| glyph(&mut atlas, *c).is_none()).unwrap();
+        assert_eq!(Some(a), glyph(&mut atlas, 'a'));
+
+        // Next frame, shelves without glyphs in use are evicted to make room, and those in use are kept.
+        atlas.end_frame();
+        assert_eq!(Some(a), glyph(&mut atlas, 'a'));
+        atlas.take_dirty();
+        assert!(glyph(&mut atlas, full).is_some());
+        assert!(atlas.take_dirty());
+        assert_eq!(Some(a), glyph(&mut atlas, 'a'));
+        assert_eq!(64, atlas.size());
+    }
+
+    #[test]
+    fn test_text_measure() {
+        let font = font();
+        let mut gewy = Gewy::new(Node::default());
+        let root_id = gewy.root_id();
+        let text = Text::new("Hello", font.clone(), 20.0);
+        let bounds = GlyphRun::new("Hello", font, 20.0).bounds;
+        let text_id = gewy.insert(root_id, Node::from_widget(text)).unwrap();
+        gewy.resize(Vec2::new(200.0, 200.0));
+        assert_eq!(bounds, gewy.get(text_id).unwrap().raw.region.size);
+
+        // Measured width is along the column.
+        gewy.get_mut(root_id).unwrap().style = Style { direction: Direction::Column, ..Default::default() };
+        gewy.resize(Vec2::new(200.0, 200.0));
+        assert_eq!(bounds, gewy.get(text_id).unwrap().raw.region.size);
+    }
+}
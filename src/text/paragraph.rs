@@ -0,0 +1,818 @@
+use std::ops::Range;
+use unicode_bidi::{BidiInfo, Level, ParagraphInfo};
+use unicode_linebreak::{linebreaks, BreakOpportunity};
+use unicode_script::{Script, UnicodeScript};
+use crate::{Font, FontMetrics, GlyphRun, PositionedGlyph, ShapedGlyph, TextDirection, Color, Rect, Vec2, shape};
+
+const EPS: f32 = 0.001;
+
+/// Text within a [`Paragraph`] that shares a font, size and color.
+/// Weights and styles are chosen by the font.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Span {
+    pub text: String,
+    pub font: Font,
+    pub size: f32,
+    /// Color of the text, or None to use the color of the style it is painted with.
+    pub color: Option<Color>
+}
+
+impl Span {
+
+    pub fn new(text: impl Into<String>, font: Font, size: f32) -> Self {
+        Self { text: text.into(), font, size, color: None }
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+/// Horizontal alignment of the lines of a [`Paragraph`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum TextAlign {
+    /// Aligns lines to the side that their paragraph starts on.
+    /// Left for left-to-right text, and right for right-to-left text.
+    #[default]
+    Start,
+    /// Aligns lines to the side that their paragraph ends on.
+    End,
+    Left,
+    Center,
+    Right,
+    /// Widens the spaces of each line to fill the width.
+    /// The last line, and lines ending in a line break, are aligned to the start.
+    Justify
+}
+
+/// Rich text made of [`Span`]s, wrapped at Unicode line break opportunities.
+///
+/// Text is shaped, so scripts like Arabic get their ligatures and contextual forms,
+/// and mixed left-to-right and right-to-left text is reordered with the Unicode bidirectional algorithm.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Paragraph {
+    pub spans: Vec<Span>,
+    /// Fonts tried in order for characters missing from the font of a span.
+    pub fallbacks: Vec<Font>,
+    pub align: TextAlign,
+    /// Base direction of the text.
+    /// If None, each line break separated paragraph takes the direction of its first strong character.
+    pub direction: Option<TextDirection>,
+    /// Multiplier of the natural height of each line.
+    pub line_height: f32,
+    /// Maximum number of lines.
+    /// Text that does not fit is cut off, and the last line ends with an ellipsis.
+    pub max_lines: Option<usize>
+}
+
+impl Default for Paragraph {
+    fn default() -> Self {
+        Self {
+            spans: Vec::new(),
+            fallbacks: Vec::new(),
+            align: TextAlign::Start,
+            direction: None,
+            line_height: 1.0,
+            max_lines: None
+        }
+    }
+}
+
+impl Paragraph {
+
+    /// Paragraph of a single span.
+    pub fn new(text: impl Into<String>, font: Font, size: f32) -> Self {
+        Self::default().with_span(Span::new(text, font, size))
+    }
+
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.spans.push(span);
+        self
+    }
+
+    pub fn with_fallback(mut self, font: Font) -> Self {
+        self.fallbacks.push(font);
+        self
+    }
+
+    pub fn with_align(mut self, align: TextAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    pub fn with_direction(mut self, direction: TextDirection) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
+    pub fn with_line_height(mut self, line_height: f32) -> Self {
+        self.line_height = line_height;
+        self
+    }
+
+    pub fn with_max_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = Some(max_lines);
+        self
+    }
+
+    /// Concatenated text of the spans.
+    pub fn text(&self) -> String {
+        self.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+
+    /// Wraps the text to a maximum width, which may be infinite.
+    /// Words wider than the maximum width are broken between characters.
+    pub fn layout(&self, max_width: f32) -> ParagraphLayout {
+        let text = self.text();
+        let chains: Vec<Vec<Font>> = self.spans.iter()
+            .map(|span| std::iter::once(span.font.clone()).chain(self.fallbacks.iter().cloned()).collect())
+            .collect();
+        let level = self.direction.map(|direction| match direction {
+            TextDirection::Ltr => Level::ltr(),
+            TextDirection::Rtl => Level::rtl()
+        });
+        let bidi = BidiInfo::new(&text, level);
+        let (chars, items) = self.shape(&text, &bidi, &chains);
+        let mut lines = self.break_lines(&text, &chars, max_width);
+
+        // Cuts off lines past the maximum, replacing the end of the last one with an ellipsis.
+        let mut ellipsis = None;
+        if let Some(max_lines) = self.max_lines.filter(|max_lines| lines.len() > *max_lines) {
+            lines.truncate(max_lines);
+            if let Some(last) = lines.last_mut() {
+                let span_index = chars[last.end - 1].span;
+                let fonts = &chains[span_index];
+                let size = self.spans[span_index].size;
+                let (font, c, count) = fonts.iter()
+                    .position(|font| font.has_glyph('…'))
+                    .map_or((0, '.', 3), |font| (font, '…', 1));
+                let id = fonts[font].glyph_id(c);
+                let advance = fonts[font].advance(id, size);
+                let width = advance * count as f32;
+                while last.end > last.start && (
+                    chars[last.end - 1].c.is_whitespace() ||
+                    advance_of(&chars[last.start..last.end]) + width > max_width + EPS
+                ) {
+                    last.end -= 1;
+                }
+                last.hard = true;
+                ellipsis = Some(Ellipsis { span: span_index, font, id, advance, count });
+            }
+        }
+
+        // Measures lines.
+        let line_count = lines.len();
+        let widths: Vec<f32> = lines.iter().enumerate().map(|(i, line)| {
+            let ellipsis_width = match ellipsis {
+                Some(ellipsis) if i == line_count - 1 => ellipsis.advance * ellipsis.count as f32,
+                _ => 0.0
+            };
+            advance_of(&chars[line.start..line.content_end(&chars)]) + ellipsis_width
+        }).collect();
+        let widest = widths.iter().copied().fold(0.0, f32::max);
+        let align_width = if max_width.is_finite() { max_width } else { widest };
+        let metrics = |span: usize, font: usize| chains[span][font].metrics(self.spans[span].size);
+
+        // Positions glyphs line by line, grouping consecutive glyphs of the same span and font.
+        let mut groups: Vec<((usize, usize), Vec<PositionedGlyph>)> = Vec::new();
+        let mut push = |key: (usize, usize), glyph: PositionedGlyph| match groups.last_mut() {
+            Some((last_key, glyphs)) if *last_key == key => glyphs.push(glyph),
+            _ => groups.push((key, vec![glyph]))
+        };
+        let mut line_layouts = Vec::with_capacity(lines.len());
+        let mut y = 0.0;
+        for (i, (line, width)) in lines.iter().zip(&widths).enumerate() {
+            let line_ellipsis = ellipsis.filter(|_| i == line_count - 1);
+            let content_end = line.content_end(&chars);
+            let start_byte = chars.get(line.start).map_or(text.len(), |c| c.byte);
+            let end_byte = chars.get(content_end).map_or(text.len(), |c| c.byte);
+            let paragraph = paragraph_at(&bidi, start_byte);
+            let is_rtl = paragraph.is_some_and(|paragraph| paragraph.level.is_rtl());
+
+            // Reorders the glyphs of the line from logical to visual order.
+            let mut glyphs: Vec<(usize, ShapedGlyph, bool)> = Vec::new();
+            if let Some(paragraph) = paragraph.filter(|_| end_byte > start_byte) {
+                let (levels, runs) = bidi.visual_runs(paragraph, start_byte..end_byte);
+                for run in runs {
+                    let mut run_items: Vec<&Item> = items.iter()
+                        .filter(|item| item.range.start < run.end && run.start < item.range.end)
+                        .collect();
+                    let run_rtl = levels[run.start].is_rtl();
+                    if run_rtl {
+                        run_items.reverse();
+                    }
+                    for item in run_items {
+                        let run_glyphs = item.glyphs.iter().filter(|glyph| run.contains(&glyph.cluster));
+                        glyphs.extend(run_glyphs.map(|glyph| (item.span, *glyph, run_rtl)));
+                    }
+                }
+            }
+
+            // Height of the line fits the tallest font on it.
+            let mut fonts: Vec<(usize, usize)> = glyphs.iter()
+                .map(|(span, glyph, _)| (*span, glyph.font))
+                .chain(line_ellipsis.map(|ellipsis| (ellipsis.span, ellipsis.font)))
+                .collect();
+            if fonts.is_empty() {
+                let span = chars[..line.end.min(chars.len())].last().map_or(0, |c| c.span);
+                fonts.push((span, 0));
+            }
+            let (mut ascent, mut descent) = (0.0_f32, 0.0_f32);
+            for (span, font) in fonts {
+                let metrics: FontMetrics = metrics(span, font);
+                ascent = ascent.max(metrics.ascent);
+                descent = descent.max(metrics.descent + metrics.line_gap);
+            }
+            let natural_height = ascent + descent;
+            let height = natural_height * self.line_height;
+            let baseline = y + (height - natural_height) / 2.0 + ascent;
+            y += height;
+
+            // Offsets the line and spaces by the alignment.
+            let remaining = align_width - width;
+            let align = match (self.align, is_rtl) {
+                (TextAlign::Start | TextAlign::Justify, false) | (TextAlign::End, true) => TextAlign::Left,
+                (TextAlign::Start, true) | (TextAlign::End, false) => TextAlign::Right,
+                (TextAlign::Justify, true) => TextAlign::Right,
+                (align, _) => align
+            };
+            let (mut x, spacing) = match align {
+                TextAlign::Center => (remaining / 2.0, 0.0),
+                TextAlign::Right => (remaining, 0.0),
+                _ => (0.0, 0.0)
+            };
+            let spacing = if self.align == TextAlign::Justify && !line.hard && remaining > 0.0 {
+                let spaces = glyphs.iter().filter(|(_, glyph, _)| char_at(&chars, glyph.cluster).c.is_whitespace()).count();
+                if spaces == 0 { spacing } else {
+                    x = 0.0;
+                    remaining / spaces as f32
+                }
+            }
+            else {
+                spacing
+            };
+
+            // Writes glyphs, with the ellipsis at the end of the line.
+            // Glyphs shaped from the same characters are gathered into clusters.
+            let ellipsis_width = line_ellipsis.map_or(0.0, |ellipsis| ellipsis.advance * ellipsis.count as f32);
+            let mut ellipsis_x = x;
+            if is_rtl {
+                x += ellipsis_width;
+            }
+            let line_x = x;
+            let mut clusters: Vec<ClusterLayout> = Vec::new();
+            for (span, glyph, rtl) in &glyphs {
+                let c = char_at(&chars, glyph.cluster).c;
+                let cluster_x = x;
+                if c.is_whitespace() {
+                    x += spacing;
+                }
+                else if !c.is_control() {
+                    let position = Vec2::new(x, baseline) + glyph.offset;
+                    push((*span, glyph.font), PositionedGlyph { id: glyph.id, position });
+                }
+                x += glyph.advance;
+                match clusters.last_mut() {
+                    Some(cluster) if cluster.range.start == glyph.cluster => cluster.width = x - cluster.x,
+                    _ => clusters.push(ClusterLayout { range: glyph.cluster..glyph.cluster, x: cluster_x, width: x - cluster_x, rtl: *rtl })
+                }
+            }
+            let mut starts: Vec<usize> = clusters.iter().map(|cluster| cluster.range.start).collect();
+            starts.sort_unstable();
+            for cluster in &mut clusters {
+                let next = starts.partition_point(|start| *start <= cluster.range.start);
+                cluster.range.end = starts.get(next).copied().unwrap_or(end_byte);
+            }
+            let line_width = x - line_x;
+
+            // Trailing whitespace hangs past the end of the line.
+            let mut trailing_x = if is_rtl { line_x } else { x };
+            for c in &chars[content_end..line.end] {
+                let range = c.byte..c.byte + c.c.len_utf8();
+                if is_rtl {
+                    trailing_x -= c.advance;
+                    clusters.push(ClusterLayout { range, x: trailing_x, width: c.advance, rtl: true });
+                }
+                else {
+                    clusters.push(ClusterLayout { range, x: trailing_x, width: c.advance, rtl: false });
+                    trailing_x += c.advance;
+                }
+            }
+            let line_end_byte = chars.get(line.end).map_or(text.len(), |c| c.byte);
+            line_layouts.push(LineLayout {
+                range: start_byte..line_end_byte,
+                top: baseline - ascent - (height - natural_height) / 2.0,
+                height,
+                baseline,
+                x: line_x,
+                width: line_width,
+                rtl: is_rtl,
+                clusters
+            });
+            if !is_rtl {
+                ellipsis_x = x;
+            }
+            if let Some(ellipsis) = line_ellipsis {
+                for i in 0..ellipsis.count {
+                    let position = Vec2::new(ellipsis_x + i as f32 * ellipsis.advance, baseline);
+                    push((ellipsis.span, ellipsis.font), PositionedGlyph { id: ellipsis.id, position });
+                }
+            }
+        }
+
+        // Packages groups as glyph runs.
+        let size = Vec2::new(widest, y);
+        let runs = groups.into_iter().map(|((span_index, font), glyphs)| {
+            let span = &self.spans[span_index];
+            let font = chains[span_index][font].clone();
+            let run = GlyphRun { font, size: span.size, glyphs, bounds: size };
+            (run, span.color)
+        }).collect();
+        ParagraphLayout { runs, size, lines: line_layouts, truncated: ellipsis.is_some() }
+    }
+
+    // Shapes the text in items of a single span, bidi level and script.
+    // Returns the characters of all spans, with the advances of the glyphs shaped from them.
+    fn shape(&self, text: &str, bidi: &BidiInfo, chains: &[Vec<Font>]) -> (Vec<Char>, Vec<Item>) {
+        let mut chars: Vec<Char> = Vec::new();
+        let mut byte = 0;
+        for (span_index, span) in self.spans.iter().enumerate() {
+            for (offset, c) in span.text.char_indices() {
+                chars.push(Char { c, advance: 0.0, span: span_index, byte: byte + offset });
+            }
+            byte += span.text.len();
+        }
+
+        // Splits the text into items.
+        // Characters common to scripts, like spaces and punctuation, join the item they are in.
+        let mut items: Vec<Item> = Vec::new();
+        let mut item_script = Script::Common;
+        for c in &chars {
+            let end = c.byte + c.c.len_utf8();
+            let level = bidi.levels[c.byte];
+            let script = c.c.script();
+            let is_common = matches!(script, Script::Common | Script::Inherited | Script::Unknown);
+            match items.last_mut() {
+                Some(item) if
+                    item.span == c.span &&
+                    bidi.levels[item.range.start] == level &&
+                    (is_common || item_script == Script::Common || item_script == script) =>
+                {
+                    item.range.end = end;
+                },
+                _ => {
+                    items.push(Item { range: c.byte..end, span: c.span, glyphs: Vec::new() });
+                    item_script = Script::Common;
+                }
+            }
+            if !is_common {
+                item_script = script;
+            }
+        }
+
+        // Shapes items, and adds the advance of each glyph to the character it starts at.
+        for item in &mut items {
+            let direction = if bidi.levels[item.range.start].is_rtl() { TextDirection::Rtl } else { TextDirection::Ltr };
+            let size = self.spans[item.span].size;
+            item.glyphs = shape(text, item.range.clone(), &chains[item.span], size, direction);
+            for glyph in &mut item.glyphs {
+                let index = chars.partition_point(|c| c.byte < glyph.cluster);
+                if chars[index].c.is_control() {
+                    glyph.advance = 0.0;
+                }
+                chars[index].advance += glyph.advance;
+            }
+        }
+        (chars, items)
+    }
+
+    // Greedily fills lines, breaking at the last opportunity that fits.
+    fn break_lines(&self, text: &str, chars: &[Char], max_width: f32) -> Vec<Line> {
+        let mut lines = Vec::new();
+        if self.spans.is_empty() {
+            return lines;
+        }
+        let mut line = Line { start: 0, end: 0, hard: false };
+        let mut width = 0.0;
+        let mut start = 0;
+        for (byte, opportunity) in linebreaks(text) {
+            let end = chars.partition_point(|c| c.byte < byte);
+            let segment = Line { start, end, hard: false };
+            start = end;
+
+            // Wraps before the segment if its content does not fit.
+            let content_width = advance_of(&chars[segment.start..segment.content_end(chars)]);
+            if line.end > line.start && width + content_width > max_width + EPS {
+                lines.push(line);
+                line = Line { start: segment.start, end: segment.start, hard: false };
+                width = 0.0;
+            }
+
+            // Words that do not fit on a line of their own are broken between characters.
+            for (i, c) in chars.iter().enumerate().take(segment.end).skip(segment.start) {
+                if line.end > line.start && !c.c.is_whitespace() && width + c.advance > max_width + EPS {
+                    lines.push(line);
+                    line = Line { start: i, end: i, hard: false };
+                    width = 0.0;
+                }
+                width += c.advance;
+                line.end = i + 1;
+            }
+            if opportunity == BreakOpportunity::Mandatory && line.end > line.start {
+                line.hard = true;
+                lines.push(line);
+                line = Line { start: segment.end, end: segment.end, hard: false };
+                width = 0.0;
+            }
+        }
+
+        // Empty text, and text ending in a line break, end with an empty line.
+        let ends_in_break = chars.last().is_none_or(|c| is_line_break(c.c));
+        if line.end > line.start || ends_in_break {
+            line.hard = true;
+            lines.push(line);
+        }
+        lines
+    }
+}
+
+/// Result of laying out a [`Paragraph`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct ParagraphLayout {
+    /// Glyph runs, along with the color of the span they came from.
+    pub runs: Vec<(GlyphRun, Option<Color>)>,
+    /// Width of the widest line, and the height of all lines.
+    pub size: Vec2,
+    pub lines: Vec<LineLayout>,
+    /// True if lines were cut off by the maximum number of lines.
+    pub truncated: bool
+}
+
+impl ParagraphLayout {
+
+    /// Index of the line a byte offset of the text is on.
+    /// Offsets where a line wraps are at the start of the next line.
+    pub fn line_index(&self, byte: usize) -> usize {
+        self.lines.partition_point(|line| line.range.start <= byte).saturating_sub(1)
+    }
+
+    /// Region of a caret placed before a byte offset of the text, with a width of zero.
+    pub fn caret(&self, byte: usize) -> Rect {
+        let Some(line) = self.lines.get(self.line_index(byte)) else { return Rect::default() };
+        Rect::new(Vec2::new(line.caret_x(byte), line.top), Vec2::new(0.0, line.height))
+    }
+
+    /// Byte offset of the text closest to a point, for placing a caret.
+    pub fn hit(&self, point: Vec2) -> usize {
+        let index = self.lines.partition_point(|line| line.top + line.height <= point.y).min(self.lines.len().saturating_sub(1));
+        let Some(line) = self.lines.get(index) else { return 0 };
+        let byte = line.hit(point.x);
+
+        // Keeps offsets on the line they were hit on, rather than the start of the next line.
+        let is_last = index + 1 == self.lines.len();
+        if byte >= line.range.end && !is_last && line.range.end > line.range.start {
+            let last = line.clusters.iter().map(|cluster| cluster.range.start).max().unwrap_or(line.range.start);
+            return last.max(line.range.start);
+        }
+        byte
+    }
+
+    /// Regions covering the text within a byte range, one or more per line.
+    pub fn selection(&self, range: Range<usize>) -> Vec<Rect> {
+        let mut rects: Vec<Rect> = Vec::new();
+        if range.is_empty() { return rects }
+        for line in &self.lines {
+            if line.range.end <= range.start || line.range.start >= range.end { continue }
+            let first = rects.len();
+            for cluster in &line.clusters {
+                let start = cluster.range.start.max(range.start);
+                let end = cluster.range.end.min(range.end);
+                if start >= end { continue }
+                let (a, b) = (cluster.caret_x(start), cluster.caret_x(end));
+                let rect = Rect::new(Vec2::new(a.min(b), line.top), Vec2::new((a - b).abs(), line.height));
+
+                // Merges touching regions of the same line.
+                match rects[first..].iter_mut().find(|other| {
+                    other.position.x - EPS <= rect.position.x + rect.size.x &&
+                    rect.position.x - EPS <= other.position.x + other.size.x
+                }) {
+                    Some(other) => {
+                        let left = other.position.x.min(rect.position.x);
+                        let right = (other.position.x + other.size.x).max(rect.position.x + rect.size.x);
+                        other.position.x = left;
+                        other.size.x = right - left;
+                    },
+                    None => rects.push(rect)
+                }
+            }
+        }
+        rects
+    }
+}
+
+/// A line of a [`ParagraphLayout`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct LineLayout {
+    /// Byte range of the text on the line, including trailing whitespace and line breaks.
+    pub range: Range<usize>,
+    pub top: f32,
+    pub height: f32,
+    pub baseline: f32,
+    /// Left side of the line's content, without trailing whitespace.
+    pub x: f32,
+    pub width: f32,
+    /// True if the line belongs to a right-to-left paragraph.
+    pub rtl: bool,
+    pub clusters: Vec<ClusterLayout>
+}
+
+impl LineLayout {
+
+    /// Horizontal position of a caret placed before a byte offset on the line.
+    pub fn caret_x(&self, byte: usize) -> f32 {
+        let cluster = self.clusters.iter().find(|cluster| cluster.range.start == byte)
+            .or_else(|| self.clusters.iter().find(|cluster| cluster.range.contains(&byte)))
+            .or_else(|| self.clusters.iter().find(|cluster| cluster.range.end == byte));
+        match cluster {
+            Some(cluster) => cluster.caret_x(byte),
+            None if self.rtl => self.x + self.width,
+            None => self.x
+        }
+    }
+
+    // Byte offset closest to a horizontal position.
+    fn hit(&self, x: f32) -> usize {
+        let nearest = self.clusters.iter().min_by(|a, b| {
+            a.distance(x).total_cmp(&b.distance(x))
+        });
+        let Some(cluster) = nearest else { return self.range.start };
+        let left_half = x < cluster.x + cluster.width / 2.0;
+        if left_half == cluster.rtl { cluster.range.end } else { cluster.range.start }
+    }
+}
+
+/// Characters of a [`LineLayout`] that were shaped into glyphs together, like ligatures.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ClusterLayout {
+    /// Byte range of the characters.
+    pub range: Range<usize>,
+    /// Left side of the cluster.
+    pub x: f32,
+    pub width: f32,
+    pub rtl: bool
+}
+
+impl ClusterLayout {
+
+    /// Horizontal position of a caret placed before a byte offset within the cluster.
+    /// Offsets within ligatures are spread evenly across them.
+    pub fn caret_x(&self, byte: usize) -> f32 {
+        let len = (self.range.end - self.range.start).max(1) as f32;
+        let t = (byte.clamp(self.range.start, self.range.end) - self.range.start) as f32 / len;
+        let t = if self.rtl { 1.0 - t } else { t };
+        self.x + self.width * t
+    }
+
+    // Horizontal distance from a position to the cluster.
+    fn distance(&self, x: f32) -> f32 {
+        if x < self.x { self.x - x }
+        else if x > self.x + self.width { x - self.x - self.width }
+        else { 0.0 }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Char {
+    c: char,
+    advance: f32,
+    span: usize,
+    byte: usize
+}
+
+// Text of a single span, bidi level and script, shaped as a whole.
+#[derive(Clone, Debug)]
+struct Item {
+    range: Range<usize>,
+    span: usize,
+    // Glyphs in visual order.
+    glyphs: Vec<ShapedGlyph>
+}
+
+// Range of characters on a line.
+#[derive(Copy, Clone, Debug)]
+struct Line {
+    start: usize,
+    end: usize,
+    // True if the line ends in a line break.
+    hard: bool
+}
+
+impl Line {
+
+    // End of the line, without trailing whitespace.
+    fn content_end(&self, chars: &[Char]) -> usize {
+        let trailing = chars[self.start..self.end].iter().rev().take_while(|c| c.c.is_whitespace()).count();
+        self.end - trailing
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Ellipsis {
+    span: usize,
+    font: usize,
+    id: u16,
+    advance: f32,
+    count: usize
+}
+
+fn advance_of(chars: &[Char]) -> f32 {
+    chars.iter().map(|c| c.advance).sum()
+}
+
+// Character starting at a byte offset.
+fn char_at(chars: &[Char], byte: usize) -> &Char {
+    &chars[chars.partition_point(|c| c.byte < byte)]
+}
+
+// Bidi paragraph containing a byte offset, or the last one if it is past the end of the text.
+fn paragraph_at<'a>(bidi: &'a BidiInfo, byte: usize) -> Option<&'a ParagraphInfo> {
+    bidi.paragraphs.iter().find(|paragraph| paragraph.range.contains(&byte)).or(bidi.paragraphs.last())
+}
+
+fn is_line_break(c: char) -> bool {
+    matches!(c, '\n' | '\r' | '\u{b}' | '\u{c}' | '\u{85}' | '\u{2028}' | '\u{2029}')
+}
+
+#[cfg(test)]
+mod test {
+    use crate::text::test::font;
+    use crate::{Paragraph, Span, TextAlign, RichText, Color, Gewy, Node, Style, Rect, Val, Vec2};
+
+    #[test]
+    fn test_wrap() {
+        let font = font();
+        let line_height = font.metrics(20.0).line_height();
+        let width = Paragraph::new("hello world", font.clone(), 20.0).layout(f32::INFINITY).size.x;
+
+        // Wraps after the last word that fits, hanging the space.
+        let layout = Paragraph::new("hello world again", font.clone(), 20.0).layout(width);
+        assert_eq!(2, layout.lines.len());
+        assert_eq!(Vec2::new(width, 2.0 * line_height), layout.size);
+        let glyphs = &layout.runs[0].0.glyphs;
+        assert_eq!(15, glyphs.len());
+        assert_eq!(0.0, glyphs[10].position.x);
+        assert!((glyphs[10].position.y - glyphs[0].position.y - line_height).abs() < 0.001);
+
+        // Line breaks are mandatory, and long words are broken between characters.
+        assert_eq!(3, Paragraph::new("a\nb\nc", font.clone(), 20.0).layout(f32::INFINITY).lines.len());
+        let layout = Paragraph::new("aaaaaaaaaa", font.clone(), 20.0).layout(width / 4.0);
+        assert!(layout.lines.len() > 1);
+        assert!(layout.size.x <= width / 4.0);
+
+        // Line height scales lines.
+        let layout = Paragraph::new("a\nb", font, 20.0).with_line_height(1.5).layout(100.0);
+        assert!((layout.size.y - 3.0 * line_height).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_align() {
+        let font = font();
+        let paragraph = Paragraph::new("one two three four", font.clone(), 20.0);
+        let natural = paragraph.layout(f32::INFINITY).size.x;
+        let width = natural * 0.75;
+        // Start and end of the first or second line.
+        let line_extent = |paragraph: &Paragraph, line: usize| {
+            let layout = paragraph.layout(width);
+            let glyphs = &layout.runs[0].0.glyphs;
+            let first_baseline = glyphs[0].position.y;
+            let baseline = if line == 0 { first_baseline } else {
+                glyphs.iter().find(|glyph| glyph.position.y != first_baseline).unwrap().position.y
+            };
+            let mut on_line = glyphs.iter().filter(|glyph| glyph.position.y == baseline);
+            let first = on_line.next().unwrap();
+            let last = on_line.next_back().unwrap();
+            (first.position.x, last.position.x + font.advance(last.id, 20.0))
+        };
+        let (left, left_end) = line_extent(&paragraph, 0);
+        assert_eq!(0.0, left);
+        let (right, right_end) = line_extent(&paragraph.clone().with_align(TextAlign::Right), 0);
+        assert!((right_end - width).abs() < 0.01);
+        let (center, _) = line_extent(&paragraph.clone().with_align(TextAlign::Center), 0);
+        assert!((center - right / 2.0).abs() < 0.01);
+        assert!(left_end < right_end);
+
+        // Justified lines fill the width, except the last.
+        let justified = paragraph.with_align(TextAlign::Justify);
+        let (justify, justify_end) = line_extent(&justified, 0);
+        assert_eq!(0.0, justify);
+        assert!((justify_end - width).abs() < 0.01);
+        let (last, last_end) = line_extent(&justified, 1);
+        assert_eq!(0.0, last);
+        assert!(last_end < width);
+    }
+
+    #[test]
+    fn test_max_lines() {
+        let font = font();
+        let paragraph = Paragraph::new("one two three four five six", font.clone(), 20.0);
+        let width = paragraph.layout(f32::INFINITY).size.x / 3.0;
+        let full = paragraph.layout(width);
+        assert!(full.lines.len() > 2);
+        assert!(!full.truncated);
+
+        let layout = paragraph.with_max_lines(2).layout(width);
+        assert_eq!(2, layout.lines.len());
+        assert!(layout.truncated);
+        assert!(layout.size.x <= width);
+        assert_eq!(2.0 * font.metrics(20.0).line_height(), layout.size.y);
+        let last = layout.runs[0].0.glyphs.last().unwrap();
+        assert_eq!(font.glyph_id('…'), last.id);
+    }
+
+    #[test]
+    fn test_spans() {
+        let font = font();
+        let paragraph = Paragraph::new("small ", font.clone(), 10.0)
+            .with_span(Span::new("big", font.clone(), 30.0).with_color(Color::RED))
+            .with_span(Span::new(" small", font.clone(), 10.0));
+        let layout = paragraph.layout(f32::INFINITY);
+        assert_eq!(3, layout.runs.len());
+        assert_eq!(None, layout.runs[0].1);
+        assert_eq!(Some(Color::RED), layout.runs[1].1);
+        assert_eq!(30.0, layout.runs[1].0.size);
+
+        // The line fits the tallest span, and spans share a baseline.
+        assert_eq!(font.metrics(30.0).line_height(), layout.size.y);
+        let baseline = layout.runs[1].0.glyphs[0].position.y;
+        assert_eq!(baseline, layout.runs[0].0.glyphs[0].position.y);
+        assert_eq!(baseline, layout.runs[2].0.glyphs[0].position.y);
+    }
+
+    #[test]
+    fn test_layout_height() {
+        let font = font();
+        let line_height = font.metrics(20.0).line_height();
+        let paragraph = Paragraph::new("one two three four five six", font, 20.0);
+        let natural = paragraph.layout(f32::INFINITY).size.x;
+        let mut gewy = Gewy::new(Node::default());
+        let root_id = gewy.root_id();
+        let text_id = gewy.insert(root_id, Node::from_widget(RichText::new(paragraph))).unwrap();
+
+        // Fits on one line when there is room.
+        gewy.resize(Vec2::new(natural + 10.0, 200.0));
+        assert_eq!(Vec2::new(natural, line_height), gewy.get(text_id).unwrap().raw.region.size);
+
+        // Wraps to the width of the parent.
+        gewy.resize(Vec2::new(natural / 2.0, 200.0));
+        let size = gewy.get(text_id).unwrap().raw.region.size;
+        assert!(size.x <= natural / 2.0);
+        assert!(size.y >= 2.0 * line_height);
+
+        // Wraps to a width given by the style, and grown widths.
+        gewy.resize(Vec2::new(natural + 10.0, 200.0));
+        gewy.get_mut(text_id).unwrap().style = Style { width: Val::Px(natural / 2.0), ..Default::default() };
+        gewy.resize(Vec2::new(natural + 10.0, 200.0));
+        assert!(gewy.get(text_id).unwrap().raw.region.size.y >= 2.0 * line_height);
+        gewy.get_mut(text_id).unwrap().style = Style { basis: Val::Px(0.0), grow: 1.0, ..Default::default() };
+        gewy.resize(Vec2::new(natural + 10.0, 200.0));
+        assert_eq!(Vec2::new(natural + 10.0, line_height), gewy.get(text_id).unwrap().raw.region.size);
+    }
+
+    #[test]
+    fn test_caret() {
+        let font = font();
+        let line_height = font.metrics(20.0).line_height();
+        let text = "ab cd\nאב";
+        let layout = Paragraph::new(text, font.clone(), 20.0).layout(f32::INFINITY);
+        assert_eq!(2, layout.lines.len());
+        let a = font.advance(font.glyph_id('a'), 20.0);
+
+        // Carets sit between characters, on the line they belong to.
+        assert_eq!(Rect::new(Vec2::ZERO, Vec2::new(0.0, line_height)), layout.caret(0));
+        assert!((layout.caret(1).position.x - a).abs() < 0.01);
+        assert_eq!(line_height, layout.caret(7).position.y);
+
+        // Right-to-left characters put the caret on their right side.
+        assert!(layout.caret(6).position.x > layout.caret(8).position.x);
+        assert!(layout.caret(8).position.x > 0.0);
+
+        // Points hit the closest caret position, staying on the line.
+        assert_eq!(1, layout.hit(Vec2::new(a * 0.9, 5.0)));
+        assert_eq!(0, layout.hit(Vec2::new(-10.0, 5.0)));
+        assert_eq!(5, layout.hit(Vec2::new(1000.0, 5.0)));
+        assert_eq!(text.len(), layout.hit(Vec2::new(-10.0, 1000.0)));
+        assert_eq!(6, layout.hit(Vec2::new(1000.0, 1000.0)));
+        for byte in [0, 1, 2, 3, 4, 5] {
+            let caret = layout.caret(byte);
+            assert_eq!(byte, layout.hit(caret.position + Vec2::new(0.0, 1.0)));
+        }
+
+        // Selections span the characters within them, one region per line.
+        let rects = layout.selection(1..8);
+        assert_eq!(2, rects.len());
+        assert!((rects[0].position.x - a).abs() < 0.01);
+        assert_eq!(line_height, rects[1].position.y);
+        assert!(layout.selection(2..2).is_empty());
+    }
+}
@@ -0,0 +1,200 @@
+use std::ops::Range;
+use rustybuzz::UnicodeBuffer;
+use crate::{Font, Vec2};
+
+// Number of characters around shaped text that are used as context for joining.
+const CONTEXT_LENGTH: usize = 5;
+
+/// Direction that text is written in.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum TextDirection {
+    #[default]
+    Ltr,
+    Rtl
+}
+
+/// A glyph produced by [`shape`].
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ShapedGlyph {
+    /// Index of the font in the fallback chain that the glyph came from.
+    pub font: usize,
+    pub id: u16,
+    /// Byte offset of the first character the glyph was shaped from.
+    pub cluster: usize,
+    /// Horizontal distance to the next glyph.
+    pub advance: f32,
+    /// Offset from the pen position, with y pointing down.
+    pub offset: Vec2
+}
+
+/// Shapes a range of text in a single script and direction, applying ligatures, contextual forms, marks and kerning.
+/// Text around the range is used as context, so that letters join across ranges.
+///
+/// Characters missing from a font are shaped with the next font of the fallback chain.
+/// Characters missing from every font are shaped as missing glyphs.
+/// Glyphs are returned in visual order, from left to right.
+pub fn shape(text: &str, range: Range<usize>, fonts: &[Font], size: f32, direction: TextDirection) -> Vec<ShapedGlyph> {
+    let mut glyphs = shape_logical(text, range, fonts, 0, size, direction);
+    if direction == TextDirection::Rtl {
+        glyphs.reverse();
+    }
+    glyphs
+}
+
+// Shapes text with a font of the chain, returning glyphs in logical order.
+fn shape_logical(text: &str, range: Range<usize>, fonts: &[Font], index: usize, size: f32, direction: TextDirection) -> Vec<ShapedGlyph> {
+    let Some(font) = fonts.get(index) else { return Vec::new() };
+    let pre_context = text[..range.start].char_indices().rev().nth(CONTEXT_LENGTH - 1).map_or(0, |(i, _)| i);
+    let post_context = text[range.end..].char_indices().nth(CONTEXT_LENGTH).map_or(text.len(), |(i, _)| range.end + i);
+    let mut buffer = UnicodeBuffer::new();
+    buffer.set_pre_context(&text[pre_context..range.start]);
+    buffer.push_str(&text[range.clone()]);
+    buffer.set_post_context(&text[range.end..post_context]);
+    buffer.set_direction(match direction {
+        TextDirection::Ltr => rustybuzz::Direction::LeftToRight,
+        TextDirection::Rtl => rustybuzz::Direction::RightToLeft
+    });
+    buffer.guess_segment_properties();
+    let output = rustybuzz::shape(&font.face(), &[], buffer);
+    let scale = font.scale(size);
+    let mut glyphs: Vec<ShapedGlyph> = output.glyph_infos()
+        .iter()
+        .zip(output.glyph_positions())
+        .map(|(info, position)| ShapedGlyph {
+            font: index,
+            id: info.glyph_id as u16,
+            cluster: range.start + info.cluster as usize,
+            advance: position.x_advance as f32 * scale,
+            offset: Vec2::new(position.x_offset as f32, -position.y_offset as f32) * scale
+        })
+        .collect();
+    if direction == TextDirection::Rtl {
+        glyphs.reverse();
+    }
+
+    // Replaces runs of missing glyphs with glyphs from the rest of the chain.
+    if index + 1 >= fonts.len() || glyphs.iter().all(|glyph| glyph.id != 0) {
+        return glyphs;
+    }
+    let mut result = Vec::with_capacity(glyphs.len());
+    let mut i = 0;
+    while i < glyphs.len() {
+        if glyphs[i].id != 0 {
+            result.push(glyphs[i]);
+            i += 1;
+            continue;
+        }
+        let missing_start = i;
+        while i < glyphs.len() && glyphs[i].id == 0 {
+            i += 1;
+        }
+        let start = glyphs[missing_start].cluster;
+        let end = glyphs.get(i).map_or(range.end, |glyph| glyph.cluster);
+        let fallback = if end > start { shape_logical(text, start..end, fonts, index + 1, size, direction) } else { Vec::new() };
+        if fallback.is_empty() || fallback.iter().all(|glyph| glyph.id == 0) {
+            result.extend_from_slice(&glyphs[missing_start..i]);
+        }
+        else {
+            result.extend(fallback);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use crate::text::test::{font, serif_font};
+    use crate::{shape, Paragraph, Span, TextDirection, Color};
+
+    #[test]
+    fn test_contextual_forms() {
+        let font = font();
+        let fonts = [font.clone()];
+        let ids = |text: &str| -> Vec<u16> {
+            shape(text, 0..text.len(), &fonts, 20.0, TextDirection::Rtl).iter().map(|glyph| glyph.id).collect()
+        };
+
+        // Beh takes its initial, medial and final forms when joined, which are visually right to left.
+        let isolated = ids("ب")[0];
+        let joined = ids("ببب");
+        assert_eq!(3, joined.len());
+        assert!(joined.iter().all(|id| *id != isolated));
+        assert_ne!(joined[0], joined[1]);
+        assert_ne!(joined[1], joined[2]);
+
+        // Lam and alef form a ligature.
+        assert_eq!(1, ids("لا").len());
+
+        // Letters join across separately shaped ranges.
+        let text = "بب";
+        let first = shape(text, 0..2, &fonts, 20.0, TextDirection::Rtl)[0];
+        assert_ne!(isolated, first.id);
+        assert_eq!(joined[2], first.id);
+    }
+
+    #[test]
+    fn test_bidi() {
+        let font = font();
+        let x_of = |layout: &crate::ParagraphLayout, c: char| {
+            let id = font.glyph_id(c);
+            layout.runs.iter()
+                .flat_map(|(run, _)| &run.glyphs)
+                .find(|glyph| glyph.id == id)
+                .unwrap()
+                .position.x
+        };
+
+        // Hebrew within left-to-right text is reversed.
+        let layout = Paragraph::new("ab אבג cd", font.clone(), 20.0).layout(f32::INFINITY);
+        assert!(x_of(&layout, 'b') < x_of(&layout, 'ג'));
+        assert!(x_of(&layout, 'ג') < x_of(&layout, 'ב'));
+        assert!(x_of(&layout, 'ב') < x_of(&layout, 'א'));
+        assert!(x_of(&layout, 'א') < x_of(&layout, 'c'));
+
+        // Right-to-left paragraphs put their first word on the right, and align to the right.
+        let paragraph = Paragraph::new("אב cd", font.clone(), 20.0);
+        let natural = paragraph.layout(f32::INFINITY).size.x;
+        let layout = paragraph.layout(natural + 50.0);
+        assert!(x_of(&layout, 'c') < x_of(&layout, 'א'));
+        assert!(x_of(&layout, 'c') >= 50.0);
+
+        // The direction can be forced.
+        let layout = Paragraph::new("ab cd", font.clone(), 20.0).with_direction(TextDirection::Rtl).layout(200.0);
+        assert!(x_of(&layout, 'a') > 100.0);
+        assert!(x_of(&layout, 'a') < x_of(&layout, 'c'));
+
+        // Truncated right-to-left lines end with an ellipsis on the left.
+        let layout = Paragraph::new("אבג אבג אבג", font.clone(), 20.0).with_max_lines(1).layout(natural);
+        assert!(layout.truncated);
+        assert!(x_of(&layout, '…') < x_of(&layout, 'א'));
+    }
+
+    #[test]
+    fn test_fallback() {
+        let serif = serif_font();
+        let sans = font();
+        assert!(!serif.has_glyph('ש'));
+
+        // Missing glyphs come from the next font of the chain.
+        let paragraph = Paragraph::new("a ש b", serif.clone(), 20.0);
+        let layout = paragraph.clone().with_fallback(sans.clone()).layout(f32::INFINITY);
+        assert_eq!(3, layout.runs.len());
+        assert_eq!(serif, layout.runs[0].0.font);
+        assert_eq!(sans, layout.runs[1].0.font);
+        assert_eq!(sans.glyph_id('ש'), layout.runs[1].0.glyphs[0].id);
+        assert_eq!(serif, layout.runs[2].0.font);
+
+        // Without a fallback, the missing glyph is used.
+        let layout = paragraph.layout(f32::INFINITY);
+        assert_eq!(1, layout.runs.len());
+        assert_eq!(0, layout.runs[0].0.glyphs[1].id);
+
+        // Spans with their own colors use the same chain.
+        let layout = Paragraph::new("a", serif.clone(), 20.0)
+            .with_span(Span::new("ש", serif, 20.0).with_color(Color::RED))
+            .with_fallback(sans.clone())
+            .layout(f32::INFINITY);
+        assert_eq!(Some(Color::RED), layout.runs[1].1);
+        assert_eq!(sans, layout.runs[1].0.font);
+    }
+}
@@ -0,0 +1,185 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use crate::NodeId;
+
+/// Source of the current time passed to [`crate::Gewy::tick`].
+pub trait Clock {
+    /// Time elapsed since a fixed point, like the creation of the clock.
+    fn now(&self) -> Duration;
+}
+
+/// Clock that measures real time from when it was created.
+#[derive(Copy, Clone, Debug)]
+pub struct SystemClock {
+    start: Instant
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        Self { start: Instant::now() }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+/// Clock that only moves when told to, for tests and replays.
+/// Clones share the same time.
+#[derive(Clone, Default, Debug)]
+pub struct ManualClock {
+    now: Rc<Cell<Duration>>
+}
+
+impl ManualClock {
+    pub fn new(now: Duration) -> Self {
+        Self { now: Rc::new(Cell::new(now)) }
+    }
+
+    pub fn set(&self, now: Duration) {
+        self.now.set(now);
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+}
+
+/// Identifies a timer among those of the same node, chosen by the widget that starts it.
+pub type TimerId = u32;
+
+// Change to the timers of a node, requested by its widget.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) enum TimerRequest {
+    Start { id: TimerId, delay: Duration, repeat: bool },
+    Stop(TimerId)
+}
+
+// Timer waiting to fire on a node.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) struct Timer {
+    pub node_id: NodeId,
+    pub id: TimerId,
+    pub deadline: Duration,
+    pub interval: Option<Duration>
+}
+
+impl Timer {
+
+    // First deadline after now of a repeating timer that ran out, skipping the intervals missed.
+    pub fn next_deadline(&self, now: Duration, interval: Duration) -> Duration {
+        const NANOS_PER_SEC: u128 = 1_000_000_000;
+        let interval = interval.as_nanos();
+        let missed = now.saturating_sub(self.deadline).as_nanos() / interval + 1;
+        let deadline = self.deadline.as_nanos() + missed * interval;
+        match u64::try_from(deadline / NANOS_PER_SEC) {
+            Ok(secs) => Duration::new(secs, (deadline % NANOS_PER_SEC) as u32),
+            Err(_) => Duration::MAX
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+    use crate::event::test::{EventLog, Recorder, logged};
+    use crate::*;
+
+    #[test]
+    fn test_timers() {
+        let log = EventLog::default();
+
+        // Starts ticking and timers when focused, and records what it receives.
+        let blinker = Recorder::new("blinker", &log)
+            .with_map(|event: &TickEvent| event.delta)
+            .with::<TimerEvent>()
+            .with_reaction(|ctl| {
+                if ctl.is_event::<FocusEvent>() {
+                    ctl.subscribe_ticks();
+                    ctl.start_timer(1, Duration::from_millis(100));
+                    ctl.start_repeating_timer(2, Duration::from_millis(30));
+                }
+                else if ctl.is_event::<UnfocusEvent>() {
+                    ctl.unsubscribe_ticks();
+                    ctl.stop_timer(2);
+                }
+            });
+        let mut gewy = Gewy::new(Node::from_widget(blinker));
+        let clock = ManualClock::default();
+        gewy.tick(clock.now()).unwrap();
+        gewy.fire_bubble(FocusEvent, gewy.root_id()).unwrap();
+
+        // Fires ticks with the time since the last, and timers that ran out, once per tick.
+        for millis in [20, 70, 110] {
+            clock.set(Duration::from_millis(millis));
+            gewy.tick(clock.now()).unwrap();
+        }
+        gewy.fire_bubble(UnfocusEvent, gewy.root_id()).unwrap();
+        clock.advance(Duration::from_millis(100));
+        gewy.tick(clock.now()).unwrap();
+        let tick = |millis| logged(("blinker", Duration::from_millis(millis)));
+        let timer = |id, millis| logged(("blinker", TimerEvent { id, now: Duration::from_millis(millis) }));
+        assert_eq!(
+            vec![
+                tick(20),
+                tick(50),
+                timer(2, 70),
+                tick(40),
+                timer(2, 110),
+                timer(1, 110)
+            ],
+            log.take()
+        );
+    }
+
+    #[test]
+    fn test_timer_gaps() {
+        let log = EventLog::default();
+        let timer = Recorder::new("timer", &log)
+            .with::<TimerEvent>()
+            .with_reaction(|ctl| {
+                if ctl.is_event::<FocusEvent>() {
+                    ctl.start_repeating_timer(1, Duration::ZERO);
+                }
+                else if ctl.is_event::<UnfocusEvent>() {
+                    ctl.stop_timer(1);
+                    ctl.start_timer(2, Duration::from_millis(100));
+                }
+            });
+        let mut gewy = Gewy::new(Node::from_widget(timer));
+        gewy.tick(Duration::ZERO).unwrap();
+        gewy.fire_bubble(FocusEvent, gewy.root_id()).unwrap();
+
+        // Repeats after gaps of many intervals.
+        let (gap, step) = (Duration::from_secs(5), Duration::from_nanos(1));
+        gewy.tick(gap).unwrap();
+        gewy.tick(gap + step).unwrap();
+
+        // Starts timers from the time of input, which may be later than the last tick.
+        gewy.mapping().set_time(Duration::from_secs(6)).unwrap();
+        gewy.fire_bubble(UnfocusEvent, gewy.root_id()).unwrap();
+        assert_eq!(Duration::from_secs(6), gewy.now());
+        gewy.tick(Duration::from_millis(6050)).unwrap();
+        gewy.tick(Duration::from_millis(6100)).unwrap();
+        let timer = |id, now| logged(("timer", TimerEvent { id, now }));
+        assert_eq!(
+            vec![timer(1, gap), timer(1, gap + step), timer(2, Duration::from_millis(6100))],
+            log.take()
+        );
+    }
+}
@@ -1,10 +1,19 @@
-use crate::{Style, Painter, Canvas};
+use crate::{Style, Brush, Painter, Canvas, RawBorder};
 
 /// Utility function for painting pane-like widgets.
 pub fn paint_pane(style: &Style, painter: &mut Painter, canvas: Canvas) {
-    let Canvas { size, corners } = canvas;
-    painter.set_color(style.color);
+    let Canvas { size, corners, border, .. } = canvas;
+    match style.fill() {
+        Brush::Solid(color) => painter.set_color(color),
+        brush => painter.set_brush(brush.scaled(size))
+    };
     painter.paint_rounded_rect(size, corners.top_left, corners.top_right, corners.bottom_right, corners.bottom_left);
+    if let Some(shadow) = style.shadow.filter(|shadow| shadow.inset) {
+        painter.paint_shadow(size, corners, shadow);
+    }
+    if border != RawBorder::default() {
+        painter.paint_border(size, corners, border, style.border.color);
+    }
 }
 
 // Iterator over a slice that either travels forwards or backwards depending on a flag.
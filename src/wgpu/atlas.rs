@@ -0,0 +1,93 @@
+use wgpu::*;
+use crate::GlyphAtlas;
+
+/// GPU representation of a [`GlyphAtlas`] as a bind group.
+pub struct GpuAtlas {
+    pub texture: Texture,
+    pub bind_group: BindGroup,
+    size: u32
+}
+
+impl GpuAtlas {
+
+    pub fn new(device: &Device, size: u32) -> Self {
+        let texture = device.create_texture(&TextureDescriptor {
+            label: Some("Glyph Atlas"),
+            size: Extent3d { width: size, height: size, depth_or_array_layers: 1 },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::R8Unorm,
+            usage: TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+            view_formats: &[]
+        });
+        let view = texture.create_view(&TextureViewDescriptor::default());
+        let sampler = device.create_sampler(&SamplerDescriptor {
+            label: Some("Glyph Atlas"),
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Glyph Atlas"),
+            layout: &Self::create_layout(device),
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&view)
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::Sampler(&sampler)
+                }
+            ]
+        });
+        Self { texture, bind_group, size }
+    }
+
+    pub fn create_layout(device: &Device) -> BindGroupLayout {
+        device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Glyph Atlas Layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false
+                    },
+                    count: None
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None
+                }
+            ]
+        })
+    }
+
+    /// Uploads the atlas, recreating the texture if the atlas grew.
+    pub fn write(&mut self, device: &Device, queue: &Queue, atlas: &GlyphAtlas) {
+        if atlas.size() != self.size {
+            *self = Self::new(device, atlas.size());
+        }
+        queue.write_texture(
+            ImageCopyTexture {
+                texture: &self.texture,
+                mip_level: 0,
+                origin: Origin3d::ZERO,
+                aspect: TextureAspect::All
+            },
+            atlas.data(),
+            ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(self.size),
+                rows_per_image: Some(self.size)
+            },
+            Extent3d { width: self.size, height: self.size, depth_or_array_layers: 1 }
+        );
+    }
+}
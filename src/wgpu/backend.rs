@@ -1,19 +1,27 @@
 use std::mem::size_of;
+use std::ops::Range;
 
 use bytemuck::{Pod, Zeroable};
 use crate::Vec2;
 use wgpu::*;
 use wgpu::util::{DeviceExt, BufferInitDescriptor};
 use crate::DrawCommand;
-use crate::wgpu::WGPUPainter;
+use crate::wgpu::{WGPUPainter, GpuPainter, PaintPass, BrushTable, GpuAtlas};
+use crate::GlyphAtlas;
 use crate::{Color, wgpu::View};
 
 pub struct WgpuBackend {
     render_pipeline: RenderPipeline,
+    composite_pipeline: RenderPipeline,
+    layer_layout: BindGroupLayout,
     painter: WGPUPainter,
+    gpu_painter: GpuPainter,
     view_format: TextureFormat,
+    view_width: u32,
+    view_height: u32,
     view_spp: u32,
-    msaa_texture_view: Option<TextureView>
+    msaa_texture_view: Option<TextureView>,
+    layers: Vec<Layer>
 }
 
 impl WgpuBackend {
@@ -26,12 +34,15 @@ impl WgpuBackend {
         view_spp: u32,
         debug: bool,
     ) -> Self {
-        let render_pipeline = create_pipeline(&device, view_format, view_spp, debug);
+        let render_pipeline = create_pipeline(device, view_format, view_spp, debug);
+        let layer_layout = Layer::create_layout(device);
+        let composite_pipeline = create_composite_pipeline(device, &layer_layout, view_format, view_spp);
         let screen_size = Vec2::new(view_width as f32, view_height as f32);
-        let painter = WGPUPainter::new(&device, screen_size);
-        let msaa_texture = if view_spp != 0 {
+        let painter = WGPUPainter::new(screen_size, view_format, device.limits().max_texture_dimension_2d);
+        let gpu_painter = painter.to_gpu(device);
+        let msaa_texture = if view_spp > 1 {
             Some(create_msaa_texture_view(
-                &device,
+                device,
                 view_format,
                 view_width,
                 view_height,
@@ -45,15 +56,24 @@ impl WgpuBackend {
         // Done
         Self {
             render_pipeline,
+            composite_pipeline,
+            layer_layout,
             painter,
+            gpu_painter,
             view_format,
+            view_width,
+            view_height,
             view_spp,
             msaa_texture_view: msaa_texture,
+            layers: Vec::new()
         }
     }
 
     pub fn resize(&mut self, view_width: u32, view_height: u32, device: &Device) {
-        if self.view_spp != 1 {
+        self.view_width = view_width;
+        self.view_height = view_height;
+        self.layers.clear();
+        if self.view_spp > 1 {
             self.msaa_texture_view = Some(create_msaa_texture_view(
                 device,
                 self.view_format,
@@ -74,51 +94,167 @@ impl WgpuBackend {
 
         // Implements draw commands
         for command in draw_commands {
-            self.painter.paint(command, device, queue);
+            self.painter.paint(command);
+        }
+        self.painter.flush(device, queue, &mut self.gpu_painter);
+
+        // Creates any offscreen layers needed.
+        let depth = self.painter.passes.iter()
+            .map(|pass| match pass {
+                PaintPass::Begin { layer } => *layer,
+                PaintPass::Draw { layer, .. } => *layer,
+                PaintPass::Composite { layer, .. } => *layer
+            })
+            .max()
+            .unwrap_or(0);
+        while self.layers.len() < depth {
+            self.layers.push(Layer::new(
+                device,
+                &self.layer_layout,
+                self.view_format,
+                self.view_width,
+                self.view_height,
+                self.view_spp
+            ));
         }
-        self.painter.flush(device, queue);
 
-        // Encodes render pass
+        // Encodes render passes
         let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor {
             label: Some("Render Encoder"),
         });
+        let clear = LoadOp::Clear(to_gpu_color(Color::BLACK, self.view_format.is_srgb()).into());
+        let transparent = LoadOp::Clear(wgpu::Color::TRANSPARENT);
+        let bind_groups = [
+            &self.gpu_painter.view.bind_group,
+            &self.gpu_painter.brush_table.bind_group,
+            &self.gpu_painter.atlas.bind_group
+        ];
+        self.encode_pass(&mut encoder, view, 0, clear, None);
+        for pass in &self.painter.passes {
+            match pass {
+                PaintPass::Begin { layer } => self.encode_pass(&mut encoder, view, *layer, transparent, None),
+                PaintPass::Draw { layer, indices } => self.encode_pass(
+                    &mut encoder,
+                    view,
+                    *layer,
+                    LoadOp::Load,
+                    Some((&self.render_pipeline, &bind_groups, indices.clone()))
+                ),
+                PaintPass::Composite { layer, indices } => self.encode_pass(
+                    &mut encoder,
+                    view,
+                    layer - 1,
+                    LoadOp::Load,
+                    Some((&self.composite_pipeline, &[&self.layers[layer - 1].bind_group], indices.clone()))
+                )
+            }
+        }
 
-        let color_attachment = if let Some(msaa_texture_view) = &self.msaa_texture_view {
-            RenderPassColorAttachment {
-                view: &msaa_texture_view,
-                resolve_target: Some(&view),
-                ops: Operations {
-                    load: LoadOp::Clear(Color::BLACK.into()),
-                    store: true
-                },
+        // Submits encoded draw calls
+        queue.submit(std::iter::once(encoder.finish()));
+        Ok(())
+    }
+
+    // Encodes a render pass targeting a layer, where 0 is the screen.
+    // Optionally draws a range of indices with a pipeline and its bind groups.
+    fn encode_pass(
+        &self,
+        encoder: &mut CommandEncoder,
+        view: &TextureView,
+        layer: usize,
+        load: LoadOp<wgpu::Color>,
+        draw: Option<(&RenderPipeline, &[&BindGroup], Range<u32>)>
+    ) {
+        let (msaa_view, view) = match layer {
+            0 => (self.msaa_texture_view.as_ref(), view),
+            _ => {
+                let layer = &self.layers[layer - 1];
+                (layer.msaa_view.as_ref(), &layer.view)
             }
+        };
+        let ops = Operations { load, store: true };
+        let color_attachment = if let Some(msaa_view) = msaa_view {
+            RenderPassColorAttachment { view: msaa_view, resolve_target: Some(view), ops }
         }
         else {
-            RenderPassColorAttachment {
-                view: &view,
-                resolve_target: None,
-                ops: Operations {
-                    load: LoadOp::Clear(Color::BLACK.into()),
-                    store: true
-                },
-            }
+            RenderPassColorAttachment { view, resolve_target: None, ops }
         };
-
         let mut render_pass = encoder.begin_render_pass(&RenderPassDescriptor {
             label: Some("Render Pass"),
             color_attachments: &[Some(color_attachment)],
             depth_stencil_attachment: None
         });
-        render_pass.set_pipeline(&self.render_pipeline);
-        render_pass.set_bind_group(0, &self.painter.gpu_view.bind_group, &[]);
-        render_pass.set_vertex_buffer(0, self.painter.gpu_mesh.vertices.slice(..));
-        render_pass.set_index_buffer(self.painter.gpu_mesh.indices.slice(..), IndexFormat::Uint32);
-        render_pass.draw_indexed(0..self.painter.gpu_mesh.index_count, 0, 0..1);
-        drop(render_pass);
+        let Some((pipeline, bind_groups, indices)) = draw else { return };
+        render_pass.set_pipeline(pipeline);
+        for (i, bind_group) in bind_groups.iter().enumerate() {
+            render_pass.set_bind_group(i as u32, bind_group, &[]);
+        }
+        render_pass.set_vertex_buffer(0, self.gpu_painter.mesh.vertices.slice(..));
+        render_pass.set_index_buffer(self.gpu_painter.mesh.indices.slice(..), IndexFormat::Uint32);
+        render_pass.draw_indexed(indices, 0, 0..1);
+    }
+}
+
+/// Offscreen texture the size of the screen that a group of shapes are painted to before being composited.
+struct Layer {
+    msaa_view: Option<TextureView>,
+    view: TextureView,
+    bind_group: BindGroup
+}
+
+impl Layer {
+
+    fn new(
+        device: &Device,
+        layout: &BindGroupLayout,
+        view_format: TextureFormat,
+        view_width: u32,
+        view_height: u32,
+        view_spp: u32
+    ) -> Self {
+        let msaa_view = if view_spp > 1 {
+            Some(create_msaa_texture_view(device, view_format, view_width, view_height, view_spp))
+        }
+        else {
+            None
+        };
+        let view = device
+            .create_texture(&TextureDescriptor {
+                label: Some("Layer Texture"),
+                size: Extent3d { width: view_width, height: view_height, depth_or_array_layers: 1 },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: view_format,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[view_format]
+            })
+            .create_view(&TextureViewDescriptor::default());
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Layer"),
+            layout,
+            entries: &[BindGroupEntry {
+                binding: 0,
+                resource: BindingResource::TextureView(&view)
+            }]
+        });
+        Self { msaa_view, view, bind_group }
+    }
 
-        // Submits encoded draw calls
-        queue.submit(std::iter::once(encoder.finish()));
-        Ok(())
+    fn create_layout(device: &Device) -> BindGroupLayout {
+        device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Layer Layout"),
+            entries: &[BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Texture {
+                    sample_type: TextureSampleType::Float { filterable: false },
+                    view_dimension: TextureViewDimension::D2,
+                    multisampled: false
+                },
+                count: None,
+            }]
+        })
     }
 }
 
@@ -157,7 +293,9 @@ pub(crate) fn create_pipeline(
     let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
         label: Some("Render Pipeline Layout"),
         bind_group_layouts: &[
-            &View::create_layout(device)
+            &View::create_layout(device),
+            &BrushTable::create_layout(device),
+            &GpuAtlas::create_layout(device)
         ],
         push_constant_ranges: &[],
     });
@@ -175,7 +313,7 @@ pub(crate) fn create_pipeline(
             entry_point: "frag_main",
             targets: &[Some(ColorTargetState {
                 format: view_format,
-                blend: Some(BlendState::REPLACE),
+                blend: Some(BlendState::PREMULTIPLIED_ALPHA_BLENDING),
                 write_mask: ColorWrites::ALL
             })]
         }),
@@ -193,16 +331,74 @@ pub(crate) fn create_pipeline(
     })
 }
 
+// Pipeline that draws an offscreen layer onto the layer beneath it.
+// Layers store premultiplied colors, which are scaled by the vertex color.
+fn create_composite_pipeline(
+    device: &Device,
+    layer_layout: &BindGroupLayout,
+    view_format: TextureFormat,
+    view_spp: u32
+) -> RenderPipeline {
+    let shader_source = include_str!("composite.wgsl");
+    let shader_module = device.create_shader_module(ShaderModuleDescriptor {
+        label: Some("Composite Shader"),
+        source: ShaderSource::Wgsl(shader_source.into()),
+    });
+    let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+        label: Some("Composite Pipeline Layout"),
+        bind_group_layouts: &[layer_layout],
+        push_constant_ranges: &[],
+    });
+    device.create_render_pipeline(&RenderPipelineDescriptor {
+        label: Some("Composite Pipeline"),
+        vertex: VertexState {
+            module: &shader_module,
+            entry_point: "vert_main",
+            buffers: &[Vertex::layout()]
+        },
+        fragment: Some(FragmentState {
+            module: &shader_module,
+            entry_point: "frag_main",
+            targets: &[Some(ColorTargetState {
+                format: view_format,
+                blend: Some(BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                write_mask: ColorWrites::ALL
+            })]
+        }),
+        primitive: PrimitiveState::default(),
+        multiview: None,
+        layout: Some(&layout),
+        depth_stencil: None,
+        multisample: MultisampleState {
+            count: view_spp,
+            ..Default::default()
+        }
+    })
+}
+
+/// Converts an sRGB [`Color`] to the premultiplied color the shaders expect.
+/// When linear is true, the color is converted to linear first, as sRGB views convert back when written to.
+/// This way, blending and interpolation happen in linear space.
+pub fn to_gpu_color(color: Color, linear: bool) -> Color {
+    let color = if linear { color.to_linear() } else { color };
+    color.premultiply()
+}
+
 #[repr(C)]
 #[derive(Pod, Zeroable, Copy, Clone, PartialEq, Default, Debug)]
 pub struct Vertex {
     pub position: Vec2,
-    pub color: Color
+    /// Color with premultiplied alpha. See [`to_gpu_color`].
+    pub color: Color,
+    /// Index of the brush in the [`crate::wgpu::BrushTable`]. 0 uses the color alone.
+    pub brush: u32,
+    /// Texel coordinates in the [`GlyphAtlas`] whose coverage scales the color.
+    pub uv: Vec2
 }
 
 impl Vertex {
     pub fn new(position: Vec2, color: Color) -> Self {
-        Self { position, color, }
+        Self { position, color, brush: 0, uv: GlyphAtlas::SOLID }
     }
 }
 
@@ -224,6 +420,18 @@ impl Vertex {
                     format: VertexFormat::Float32x4,
                     offset: 8,
                     shader_location: 1
+                },
+                // Brush (4 bytes)
+                VertexAttribute {
+                    format: VertexFormat::Uint32,
+                    offset: 24,
+                    shader_location: 2
+                },
+                // UV (8 bytes)
+                VertexAttribute {
+                    format: VertexFormat::Float32x2,
+                    offset: 28,
+                    shader_location: 3
                 }
             ]
         }
@@ -297,5 +505,28 @@ pub(crate) fn write_to_buffer(
             mapped_at_creation: false,
         })
     }
-    queue.write_buffer(&buffer, 0, source);
-}
\ No newline at end of file
+    queue.write_buffer(buffer, 0, source);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Color;
+    use crate::wgpu::to_gpu_color;
+
+    #[test]
+    fn test_to_gpu_color() {
+        let color = Color::new(0.5, 1.0, 0.0, 0.5);
+
+        // Non-sRGB views only premultiply.
+        let gpu_color = to_gpu_color(color, false);
+        assert_eq!(Color::new(0.25, 0.5, 0.0, 0.5), gpu_color);
+
+        // sRGB views convert to linear before premultiplying.
+        let gpu_color = to_gpu_color(color, true);
+        assert!((gpu_color.r() - 0.214_041 * 0.5).abs() < 0.0001);
+        assert_eq!(0.5, gpu_color.g());
+        assert_eq!(0.0, gpu_color.b());
+        assert_eq!(0.5, gpu_color.a());
+        assert_eq!(Color::WHITE, to_gpu_color(Color::WHITE, true));
+    }
+}
@@ -0,0 +1,195 @@
+use bytemuck::{Pod, Zeroable};
+use wgpu::{*, util::{BufferInitDescriptor, DeviceExt}};
+use crate::{Brush, Color, Vec2};
+use crate::wgpu::{write_to_buffer, to_gpu_color};
+
+/// Brushes in a format the shader can evaluate.
+/// Index 0 is always a solid brush, which uses the color of the vertex.
+#[derive(Clone, Default, Debug)]
+pub struct BrushTable {
+    brushes: Vec<BrushEntry>,
+    stops: Vec<StopEntry>,
+    linear: bool
+}
+
+impl BrushTable {
+
+    const SOLID: u32 = 0;
+    const LINEAR: u32 = 1;
+    const RADIAL: u32 = 2;
+
+    /// Creates a table whose stop colors are converted to linear if specified.
+    /// See [`to_gpu_color`].
+    pub fn new(linear: bool) -> Self {
+        let mut slf = Self { linear, ..Default::default() };
+        slf.clear();
+        slf
+    }
+
+    /// Adds a brush, and returns its index.
+    /// Solid brushes are not stored, and always return index 0.
+    pub fn push(&mut self, brush: &Brush) -> u32 {
+        let (kind, start, end, stops) = match brush {
+            Brush::Solid(_) => return 0,
+            Brush::Linear(gradient) => (Self::LINEAR, gradient.start, gradient.end, &gradient.stops),
+            Brush::Radial(gradient) => (Self::RADIAL, gradient.center, gradient.radius, &gradient.stops),
+        };
+        let index = self.brushes.len() as u32;
+        self.brushes.push(BrushEntry {
+            kind,
+            stop_start: self.stops.len() as u32,
+            stop_count: stops.len() as u32,
+            _padding: 0,
+            start,
+            end
+        });
+        let linear = self.linear;
+        self.stops.extend(stops.iter().map(|stop| StopEntry {
+            color: to_gpu_color(stop.color, linear),
+            offset: stop.offset,
+            _padding: [0.0; 3]
+        }));
+        index
+    }
+
+    /// Removes all brushes but the solid one.
+    pub fn clear(&mut self) {
+        self.brushes.clear();
+        self.stops.clear();
+        self.brushes.push(BrushEntry { kind: Self::SOLID, ..Default::default() });
+        self.stops.push(StopEntry::default());
+    }
+
+    pub fn create_layout(device: &Device) -> BindGroupLayout {
+        let entry = |binding| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::FRAGMENT,
+            ty: BindingType::Buffer {
+                ty: BufferBindingType::Storage { read_only: true },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Brush Layout"),
+            entries: &[entry(0), entry(1)]
+        })
+    }
+
+    pub fn to_gpu(&self, device: &Device) -> GpuBrushTable {
+        let brushes = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("Brushes"),
+            contents: bytemuck::cast_slice(&self.brushes),
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST
+        });
+        let stops = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("Brush Stops"),
+            contents: bytemuck::cast_slice(&self.stops),
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST
+        });
+        let bind_group = create_bind_group(device, &brushes, &stops);
+        GpuBrushTable { brushes, stops, bind_group }
+    }
+
+    pub fn write_to_gpu(&self, device: &Device, queue: &Queue, table: &mut GpuBrushTable) {
+        let brushes_size = table.brushes.size();
+        let stops_size = table.stops.size();
+        write_to_buffer(&mut table.brushes, bytemuck::cast_slice(&self.brushes), Some("Brushes"), device, queue);
+        write_to_buffer(&mut table.stops, bytemuck::cast_slice(&self.stops), Some("Brush Stops"), device, queue);
+
+        // Buffers are recreated when they grow, so the bind group must be too.
+        if table.brushes.size() != brushes_size || table.stops.size() != stops_size {
+            table.bind_group = create_bind_group(device, &table.brushes, &table.stops);
+        }
+    }
+}
+
+/// GPU representation of a [`BrushTable`] as a bind group.
+pub struct GpuBrushTable {
+    pub brushes: Buffer,
+    pub stops: Buffer,
+    pub bind_group: BindGroup
+}
+
+fn create_bind_group(device: &Device, brushes: &Buffer, stops: &Buffer) -> BindGroup {
+    device.create_bind_group(&BindGroupDescriptor {
+        label: Some("Brushes"),
+        layout: &BrushTable::create_layout(device),
+        entries: &[
+            BindGroupEntry {
+                binding: 0,
+                resource: brushes.as_entire_binding()
+            },
+            BindGroupEntry {
+                binding: 1,
+                resource: stops.as_entire_binding()
+            }
+        ]
+    })
+}
+
+// Brush as laid out in the shader.
+// For radial gradients, start is the center and end is the radius.
+#[repr(C)]
+#[derive(Pod, Zeroable, Copy, Clone, PartialEq, Default, Debug)]
+struct BrushEntry {
+    kind: u32,
+    stop_start: u32,
+    stop_count: u32,
+    _padding: u32,
+    start: Vec2,
+    end: Vec2
+}
+
+// Color stop as laid out in the shader.
+#[repr(C)]
+#[derive(Pod, Zeroable, Copy, Clone, PartialEq, Default, Debug)]
+struct StopEntry {
+    color: Color,
+    offset: f32,
+    _padding: [f32; 3]
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Brush, ColorStop, Color, Vec2};
+    use crate::wgpu::{BrushTable, to_gpu_color};
+    use super::{BrushEntry, StopEntry};
+
+    #[test]
+    fn test_brush_table() {
+        let mut table = BrushTable::new(true);
+        let linear = Brush::linear(Vec2::ZERO, Vec2::X, [ColorStop::new(0.0, Color::RED), ColorStop::new(1.0, Color::BLUE)]);
+        let radial = Brush::radial(Vec2::ONE, Vec2::splat(2.0), [ColorStop::new(0.5, Color::new(1.0, 1.0, 1.0, 0.5))]);
+
+        // Solid brushes share the first entry, and gradients are appended with their stops after those before them.
+        assert_eq!(0, table.push(&Brush::Solid(Color::RED)));
+        assert_eq!(1, table.push(&linear));
+        assert_eq!(2, table.push(&radial));
+        assert_eq!(
+            vec![
+                BrushEntry { kind: BrushTable::SOLID, ..Default::default() },
+                BrushEntry { kind: BrushTable::LINEAR, stop_start: 1, stop_count: 2, _padding: 0, start: Vec2::ZERO, end: Vec2::X },
+                BrushEntry { kind: BrushTable::RADIAL, stop_start: 3, stop_count: 1, _padding: 0, start: Vec2::ONE, end: Vec2::splat(2.0) }
+            ],
+            table.brushes
+        );
+
+        // Stop colors are converted for the view.
+        let stop = |offset, color| StopEntry { color: to_gpu_color(color, true), offset, _padding: [0.0; 3] };
+        assert_eq!(
+            vec![
+                StopEntry::default(),
+                stop(0.0, Color::RED),
+                stop(1.0, Color::BLUE),
+                stop(0.5, Color::new(1.0, 1.0, 1.0, 0.5))
+            ],
+            table.stops
+        );
+
+        table.clear();
+        assert_eq!(1, table.brushes.len());
+        assert_eq!(1, table.stops.len());
+    }
+}
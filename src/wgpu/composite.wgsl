@@ -0,0 +1,24 @@
+@group(0) @binding(0)
+var layer: texture_2d<f32>;
+
+struct VertexInput {
+    @location(0) position: vec2<f32>,
+    @location(1) color: vec4<f32>
+};
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec4<f32>
+};
+
+// Positions are already in clip space.
+@vertex
+fn vert_main(input: VertexInput) -> VertexOutput {
+    return VertexOutput(vec4<f32>(input.position, 0.0, 1.0), input.color);
+}
+
+// Layers are the size of the screen, so texels line up with fragments.
+@fragment
+fn frag_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return textureLoad(layer, vec2<i32>(in.position.xy), 0) * in.color;
+}
@@ -1,6 +1,10 @@
+mod atlas;
 mod backend;
+mod brush;
 mod paint;
 mod view;
+pub use atlas::*;
 pub use backend::*;
+pub use brush::*;
 pub use paint::*;
 pub use view::*;
\ No newline at end of file
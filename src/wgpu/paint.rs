@@ -1,57 +1,152 @@
 use wgpu::*;
-use crate::{Color, DrawCommand};
-use crate::wgpu::{Mesh, GpuMesh, View, GpuView, Vertex};
+use crate::{Color, Brush, DrawCommand, RawCorners, RawSides, BorderColor, Shadow, GlyphRun, GlyphAtlas};
+use crate::wgpu::{Mesh, GpuMesh, View, GpuView, Vertex, BrushTable, GpuBrushTable, GpuAtlas, to_gpu_color};
 use crate::Vec2;
 use std::f32::consts::{FRAC_PI_2, PI};
 use std::f32::consts::TAU;
+use std::ops::Range;
 
 /// WGPU-backend for [`crate::Painter`]
 pub struct WGPUPainter {
+    /// Current color, converted with [`to_gpu_color`].
     pub color: Color,
     pub translation: Vec2,
-    pub(crate) gpu_mesh: GpuMesh,
-    pub(crate) gpu_view: GpuView,
+    pub(crate) passes: Vec<PaintPass>,
     mesh: Mesh,
     view: View,
+    brush_table: BrushTable,
+    atlas: GlyphAtlas,
+    brush: u32,
+    linear: bool,
     index: u32,
-    polygon_scale: f32
+    polygon_scale: f32,
+    pending_passes: Vec<PaintPass>,
+    layer: usize,
+    batch_start: u32
+}
+
+/// GPU resources written to by a [`WGPUPainter`].
+pub(crate) struct GpuPainter {
+    pub mesh: GpuMesh,
+    pub view: GpuView,
+    pub brush_table: GpuBrushTable,
+    pub atlas: GpuAtlas
+}
+
+/// A step in rendering a frame.
+/// Layer 0 is the screen, and each nested layer is an offscreen texture the size of the screen.
+#[derive(Clone, PartialEq, Debug)]
+pub(crate) enum PaintPass {
+    /// Clears a layer to transparent.
+    Begin { layer: usize },
+    /// Draws a range of indices to a layer.
+    Draw { layer: usize, indices: Range<u32> },
+    /// Draws a layer onto the layer beneath it, using a range of indices that cover the screen.
+    Composite { layer: usize, indices: Range<u32> }
 }
 
 impl WGPUPainter {
     
-    pub fn new(device: &Device, screen_size: Vec2) -> Self {
-        let mesh = Mesh::new();
-        let gpu_mesh = mesh.to_gpu(device);
-        let view = View::new(screen_size, Vec2::ZERO, 1.0);
-        let gpu_view = view.to_gpu(&device);
+    /// Creates a painter for a view.
+    /// Colors are converted to linear if the view format is sRGB.
+    /// The glyph atlas grows up to the maximum texture size.
+    pub fn new(screen_size: Vec2, view_format: TextureFormat, max_texture_size: u32) -> Self {
         Self {
             translation: Vec2::ZERO,
             color: Color::WHITE,
-            mesh,
-            gpu_mesh,
-            view,
-            gpu_view,
+            mesh: Mesh::new(),
+            view: View::new(screen_size, Vec2::ZERO, 1.0),
+            brush_table: BrushTable::new(view_format.is_srgb()),
+            atlas: GlyphAtlas::new(512, max_texture_size.min(4096)),
+            brush: 0,
+            linear: view_format.is_srgb(),
             index: 0,
-            polygon_scale: 1.0
+            polygon_scale: 1.0,
+            passes: Vec::new(),
+            pending_passes: Vec::new(),
+            layer: 0,
+            batch_start: 0
+        }
+    }
+
+    /// Creates the GPU resources the painter writes to.
+    pub(crate) fn to_gpu(&self, device: &Device) -> GpuPainter {
+        GpuPainter {
+            mesh: self.mesh.to_gpu(device),
+            view: self.view.to_gpu(device),
+            brush_table: self.brush_table.to_gpu(device),
+            atlas: GpuAtlas::new(device, self.atlas.size())
         }
     }
 
-    pub fn paint(&mut self, command: DrawCommand, device: &Device, queue: &Queue) {
+    pub fn paint(&mut self, command: DrawCommand) {
         match command {
             DrawCommand::Translation(translation) => self.translation = translation,
-            DrawCommand::Color(color) => self.color = color,
+            DrawCommand::Color(color) => self.set_color(color),
+            DrawCommand::Brush(brush) => self.set_brush(&brush),
             DrawCommand::Circle { radius } => self.circle(radius),
             DrawCommand::Rect { size } => self.rect(size),
             DrawCommand::RoundedRect { size, top_left, top_right, bottom_right, bottom_left } => self.rounded_rect(size, top_left, top_right, bottom_right, bottom_left),
-            DrawCommand::Resize { size, translation, scale } => self.resize(size, translation, scale, device, queue)
+            DrawCommand::Border { size, corners, widths, colors } => self.border(size, corners, widths, colors),
+            DrawCommand::Shadow { size, corners, shadow } => self.shadow(size, corners, shadow),
+            DrawCommand::Glyphs(run) => self.glyphs(&run),
+            DrawCommand::PushLayer => self.push_layer(),
+            DrawCommand::PopLayer { opacity } => self.pop_layer(opacity),
+            DrawCommand::Resize { size, translation, scale } => self.resize(size, translation, scale)
         }
     }
 
+    /// Sets the color of future shapes.
+    pub fn set_color(&mut self, color: Color) {
+        self.color = to_gpu_color(color, self.linear);
+        self.brush = 0;
+    }
+
+    /// Sets the brush of future shapes.
+    /// Gradient coordinates are in the same space as translations.
+    pub fn set_brush(&mut self, brush: &Brush) {
+        match brush {
+            Brush::Solid(color) => self.set_color(*color),
+            _ => {
+                self.color = Color::WHITE;
+                self.brush = self.brush_table.push(brush);
+            }
+        }
+    }
+
+    /// Redirects future shapes to a new offscreen layer.
+    pub fn push_layer(&mut self) {
+        self.end_batch();
+        self.layer += 1;
+        self.pending_passes.push(PaintPass::Begin { layer: self.layer });
+    }
+
+    /// Composites the current layer onto the one beneath it with an opacity.
+    pub fn pop_layer(&mut self, opacity: f32) {
+        if self.layer == 0 { return }
+        self.end_batch();
+        let start = self.mesh.indices.len() as u32;
+        let i = self.index;
+        let color = Color::new(opacity, opacity, opacity, opacity);
+        self.mesh.vertices.extend([
+            Vertex::new(Vec2::new(-1.0, -1.0), color),
+            Vertex::new(Vec2::new(1.0, -1.0), color),
+            Vertex::new(Vec2::new(1.0, 1.0), color),
+            Vertex::new(Vec2::new(-1.0, 1.0), color)
+        ]);
+        self.mesh.indices.extend([i, i+1, i+2, i+2, i+3, i]);
+        self.index += 4;
+        let end = self.mesh.indices.len() as u32;
+        self.pending_passes.push(PaintPass::Composite { layer: self.layer, indices: start..end });
+        self.layer -= 1;
+        self.batch_start = end;
+    }
+
     /// Paints a triangle.
     pub fn triangle(&mut self, points: [Vec2; 3]) {
         let i = self.index;
         self.mesh.vertices.extend(self.points_to_vertices(points));
-        self.mesh.indices.extend([i+0, i+1, i+2]);
+        self.mesh.indices.extend([i, i+1, i+2]);
         self.index += 3;
     }
 
@@ -91,6 +186,77 @@ impl WGPUPainter {
         drop(shape);
     }
 
+    /// Paints a border along the inside edges of a rectangle with rounded corners.
+    /// Each corner is split halfway between the colors of the two sides it joins.
+    pub fn border(&mut self, size: Vec2, corners: RawCorners, widths: RawSides, colors: BorderColor) {
+        let widths = RawSides {
+            top: widths.top().min(size.y / 2.0),
+            right: widths.right().min(size.x / 2.0),
+            bottom: widths.bottom().min(size.y / 2.0),
+            left: widths.left().min(size.x / 2.0)
+        };
+        let outer = Outline::new(Vec2::ZERO, size, corners);
+        let inner = outer.inset(widths);
+        let colors = [colors.top, colors.right, colors.bottom, colors.left];
+        self.ring(outer, inner, colors, colors);
+    }
+
+    /// Paints a shadow of a rectangle with rounded corners.
+    /// Outset shadows are painted around the rectangle, while inset shadows are painted inside of it.
+    /// Blurring is approximated by fading the color out linearly across the blur radius.
+    pub fn shadow(&mut self, size: Vec2, corners: RawCorners, shadow: Shadow) {
+        let blur = shadow.blur.max(0.0);
+        let solid = [shadow.color; 4];
+        let clear = [shadow.color * Color::new(1.0, 1.0, 1.0, 0.0); 4];
+        if shadow.inset {
+            let bounds = Outline::new(Vec2::ZERO, size, corners);
+            let hole = bounds.grow(-shadow.spread).translate(shadow.offset);
+            let hole_outer = hole.grow(blur).clamp(bounds);
+            let hole_inner = hole.grow(-blur).clamp(bounds);
+            self.ring(bounds, hole_outer, solid, solid);
+            self.ring(hole_outer, hole_inner, solid, clear);
+        }
+        else {
+            let shape = Outline::new(shadow.offset, size, corners).grow(shadow.spread);
+            let outer = shape.grow(blur);
+            let inner = shape.grow(-blur);
+            self.outline(inner, shadow.color);
+            self.ring(outer, inner, clear, solid);
+        }
+    }
+
+    /// Paints glyphs as quads textured by the glyph atlas.
+    /// Glyphs are rasterized at the scale of the view, and snapped to its pixels.
+    pub fn glyphs(&mut self, run: &GlyphRun) {
+        let scale = self.polygon_scale;
+        for glyph in &run.glyphs {
+            let origin = (self.translation + glyph.position) * scale;
+            let origin = Vec2::new(origin.x.floor(), origin.y.round());
+            let subpixel_offset = (self.translation.x + glyph.position.x) * scale - origin.x;
+            let Some(atlas_glyph) = self.atlas.glyph(&run.font, glyph.id, run.size * scale, subpixel_offset) else {
+                continue
+            };
+            let top_left = (origin + atlas_glyph.offset) / scale;
+            let size = atlas_glyph.size.as_vec2();
+            let uv = atlas_glyph.position.as_vec2();
+            let i = self.index;
+            let (color, brush) = (self.color, self.brush);
+            self.mesh.vertices.extend([
+                Vec2::new(0.0, 0.0),
+                Vec2::new(size.x, 0.0),
+                Vec2::new(size.x, size.y),
+                Vec2::new(0.0, size.y)
+            ].map(|corner| Vertex {
+                position: top_left + corner / scale,
+                color,
+                brush,
+                uv: uv + corner
+            }));
+            self.mesh.indices.extend([i, i+1, i+2, i+2, i+3, i]);
+            self.index += 4;
+        }
+    }
+
     /// Paints a circle with a radius.
     /// The number of points scales with the radius.
     pub fn circle(&mut self, radius: f32) {
@@ -109,6 +275,8 @@ impl WGPUPainter {
             self.mesh.vertices.push(Vertex {
                 position: position + self.translation,
                 color: self.color,
+                brush: self.brush,
+                uv: GlyphAtlas::SOLID
             });
         }
 
@@ -126,29 +294,231 @@ impl WGPUPainter {
     pub(crate) fn quad(&mut self, points: [Vec2; 4]) {
         let i = self.index;
         self.mesh.vertices.extend(self.points_to_vertices(points));
-        self.mesh.indices.extend([i+0, i+1, i+2, i+2, i+3, i+0]);
+        self.mesh.indices.extend([i, i+1, i+2, i+2, i+3, i]);
+        self.index += 4;
+    }
+
+    // Paints a quad with a color per point.
+    pub(crate) fn colored_quad(&mut self, points: [Vec2; 4], colors: [Color; 4]) {
+        let i = self.index;
+        let (translation, linear) = (self.translation, self.linear);
+        self.mesh.vertices.extend((0..4).map(|j| Vertex::new(points[j] + translation, to_gpu_color(colors[j], linear))));
+        self.mesh.indices.extend([i, i+1, i+2, i+2, i+3, i]);
         self.index += 4;
     }
 
+    // Fills the inside of an outline.
+    pub(crate) fn outline(&mut self, outline: Outline, color: Color) {
+        if outline.size.x <= 0.0 || outline.size.y <= 0.0 { return }
+        let prev_color = self.color;
+        self.color = to_gpu_color(color, self.linear);
+        let polygon_scale = self.polygon_scale;
+        let mut shape = self.shape();
+        for corner in 0..4 {
+            let vertex_count = outline.vertex_count(corner, polygon_scale);
+            let divisor = (vertex_count - 1).max(1) as f32;
+            for i in 0..vertex_count {
+                shape.point(outline.arc_point(corner, i as f32 / divisor));
+            }
+        }
+        drop(shape);
+        self.color = prev_color;
+    }
+
+    // Paints the area between two outlines, where the inner outline is contained in the outer outline.
+    // Colors are listed per side, clockwise from the top.
+    // Corners are split halfway between the colors of the two sides they join.
+    pub(crate) fn ring(&mut self, outer: Outline, inner: Outline, outer_colors: [Color; 4], inner_colors: [Color; 4]) {
+
+        // Computes outer/inner point pairs clockwise, starting from the top left corner.
+        let mut pairs: Vec<(Vec2, Vec2, Color, Color)> = Vec::new();
+        for corner in 0..4 {
+            let (from, to) = ((corner + 3) % 4, corner);
+            let vertex_count = outer.vertex_count(corner, self.polygon_scale)
+                .max(inner.vertex_count(corner, self.polygon_scale)) | 1;
+            let middle = vertex_count / 2;
+            let divisor = (vertex_count - 1).max(1) as f32;
+            for i in 0..vertex_count {
+                let ratio = if vertex_count == 1 { 0.5 } else { i as f32 / divisor };
+                let outer_point = outer.arc_point(corner, ratio);
+                let inner_point = inner.arc_point(corner, ratio);
+                if i <= middle {
+                    pairs.push((outer_point, inner_point, outer_colors[from], inner_colors[from]));
+                }
+                if i >= middle {
+                    pairs.push((outer_point, inner_point, outer_colors[to], inner_colors[to]));
+                }
+            }
+        }
+
+        // Paints segments between consecutive pairs.
+        for i in 0..pairs.len() {
+            let (outer_a, inner_a, outer_color_a, inner_color_a) = pairs[i];
+            let (outer_b, inner_b, outer_color_b, inner_color_b) = pairs[(i + 1) % pairs.len()];
+            if outer_a == inner_a && outer_b == inner_b { continue }
+            if outer_a == outer_b && inner_a == inner_b { continue }
+            self.colored_quad(
+                [outer_a, outer_b, inner_b, inner_a],
+                [outer_color_a, outer_color_b, inner_color_b, inner_color_a]
+            );
+        }
+    }
+
     pub(crate) fn shape(&mut self) -> ShapePainter<'_> {
         ShapePainter { painter: self }
     }
 
-    pub(crate) fn flush(&mut self, device: &Device, queue: &Queue) {
-        self.mesh.write_to_gpu(device, queue, &mut self.gpu_mesh);
+    /// Writes what was painted since the last flush to the GPU, and starts over.
+    pub(crate) fn flush(&mut self, device: &Device, queue: &Queue, gpu: &mut GpuPainter) {
+        self.finish();
+        self.mesh.write_to_gpu(device, queue, &mut gpu.mesh);
+        self.brush_table.write_to_gpu(device, queue, &mut gpu.brush_table);
+        self.view.write_to_gpu(device, queue, &mut gpu.view);
+        if self.atlas.take_dirty() {
+            gpu.atlas.write(device, queue, &self.atlas);
+        }
+        self.atlas.end_frame();
         self.mesh.clear();
+        self.brush_table.clear();
         self.index = 0;
     }
 
-    pub(crate) fn resize(&mut self, size: Vec2, translation: Vec2, scale: f32, device: &Device, queue: &Queue) {
+    pub(crate) fn resize(&mut self, size: Vec2, translation: Vec2, scale: f32) {
         self.view = View::new(size, translation, scale);
         self.polygon_scale = scale;
-        self.view.write_to_gpu(device, queue, &mut self.gpu_view)
+    }
+
+    // Closes any layers left open, and turns pending passes into the passes to render.
+    fn finish(&mut self) {
+        while self.layer > 0 {
+            self.pop_layer(1.0);
+        }
+        self.end_batch();
+        self.passes = std::mem::take(&mut self.pending_passes);
+        self.batch_start = 0;
+    }
+
+    // Records a draw pass for indices written since the last pass.
+    fn end_batch(&mut self) {
+        let end = self.mesh.indices.len() as u32;
+        if end > self.batch_start {
+            self.pending_passes.push(PaintPass::Draw { layer: self.layer, indices: self.batch_start..end });
+        }
+        self.batch_start = end;
     }
 
     // Translates points and turns them into vertices.
     fn points_to_vertices<const N: usize>(&self, points: [Vec2; N]) -> [Vertex; N] {
-        points.map(|point| Vertex::new(point + self.translation, self.color))
+        points.map(|point| Vertex {
+            position: point + self.translation,
+            color: self.color,
+            brush: self.brush,
+            uv: GlyphAtlas::SOLID
+        })
+    }
+}
+
+/// Outline of a rectangle with elliptical corners.
+/// Corners are listed clockwise, starting from the top left.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub(crate) struct Outline {
+    position: Vec2,
+    size: Vec2,
+    radii: [Vec2; 4]
+}
+
+impl Outline {
+
+    // Angles where each corner's arc starts.
+    const ANGLES: [f32; 4] = [PI, 3.0 * FRAC_PI_2, 0.0, FRAC_PI_2];
+
+    pub fn new(position: Vec2, size: Vec2, corners: RawCorners) -> Self {
+        let max_radius = (size / 2.0).min_element().max(0.0);
+        let radius = |r: f32| Vec2::splat(r.clamp(0.0, max_radius));
+        Self {
+            position,
+            size,
+            radii: [
+                radius(corners.top_left),
+                radius(corners.top_right),
+                radius(corners.bottom_right),
+                radius(corners.bottom_left)
+            ]
+        }
+    }
+
+    // Moves the edges inwards, shrinking the corners to follow.
+    pub fn inset(self, sides: RawSides) -> Self {
+        let top_left = Vec2::new(sides.left, sides.top);
+        let bottom_right = Vec2::new(sides.right, sides.bottom);
+        let mut size = self.size - top_left - bottom_right;
+        let mut position = self.position + top_left;
+
+        // Collapses to the center line if the edges crossed.
+        if size.x < 0.0 {
+            position.x += size.x / 2.0;
+            size.x = 0.0;
+        }
+        if size.y < 0.0 {
+            position.y += size.y / 2.0;
+            size.y = 0.0;
+        }
+        let r = self.radii;
+        let max_radius = size / 2.0;
+        let radius = |r: Vec2| r.max(Vec2::ZERO).min(max_radius);
+        Self {
+            position,
+            size,
+            radii: [
+                radius(r[0] - Vec2::new(sides.left, sides.top)),
+                radius(r[1] - Vec2::new(sides.right, sides.top)),
+                radius(r[2] - Vec2::new(sides.right, sides.bottom)),
+                radius(r[3] - Vec2::new(sides.left, sides.bottom))
+            ]
+        }
+    }
+
+    // Moves the edges outwards by the amount specified, or inwards if negative.
+    pub fn grow(self, amount: f32) -> Self {
+        self.inset(RawSides { top: -amount, right: -amount, bottom: -amount, left: -amount })
+    }
+
+    pub fn translate(mut self, offset: Vec2) -> Self {
+        self.position += offset;
+        self
+    }
+
+    // Shrinks the outline to fit within another.
+    pub fn clamp(self, bounds: Outline) -> Self {
+        let top_left = self.position.max(bounds.position);
+        let bottom_right = (self.position + self.size).min(bounds.position + bounds.size);
+        let size = (bottom_right - top_left).max(Vec2::ZERO);
+        let max_radius = size / 2.0;
+        Self {
+            position: top_left,
+            size,
+            radii: self.radii.map(|r| r.min(max_radius))
+        }
+    }
+
+    // Point along a corner's arc, where ratio 0.0 is the start and 1.0 is the end.
+    pub fn arc_point(&self, corner: usize, ratio: f32) -> Vec2 {
+        let r = self.radii[corner];
+        let (p, s) = (self.position, self.size);
+        let center = match corner {
+            0 => p + r,
+            1 => Vec2::new(p.x + s.x - r.x, p.y + r.y),
+            2 => p + s - r,
+            _ => Vec2::new(p.x + r.x, p.y + s.y - r.y)
+        };
+        let direction = Vec2::from_angle(Self::ANGLES[corner] + FRAC_PI_2 * ratio);
+        center + direction * r
+    }
+
+    // Number of vertices used to paint a corner.
+    pub fn vertex_count(&self, corner: usize, polygon_scale: f32) -> u32 {
+        let radius = self.radii[corner].max_element();
+        if radius <= 0.0 { 1 } else { radius_to_quarter_vertex_count(radius * polygon_scale) }
     }
 }
 
@@ -163,7 +533,7 @@ impl<'p> ShapePainter<'p> {
         self.painter.mesh.vertices.push(v);
     }
     pub fn point(&mut self, point: Vec2) {
-        self.vertex(Vertex { position: point, color: self.painter.color });
+        self.vertex(Vertex { position: point, color: self.painter.color, brush: self.painter.brush, uv: GlyphAtlas::SOLID });
     }
     pub fn quarter_circle(&mut self, center: Vec2, radius: f32, radians_offset: f32) {
         
@@ -180,7 +550,7 @@ impl<'p> ShapePainter<'p> {
             let divisor = (vertex_count - 1) as f32;
             for i in 0..vertex_count {
                 let i = i as f32;
-                let ratio = i as f32 / divisor;
+                let ratio = i / divisor;
                 let radians = FRAC_PI_2 * ratio;
                 self.point(Vec2::from_angle(radians + radians_offset) * radius);
             }
@@ -227,4 +597,27 @@ fn radius_to_vertex_count(radius: f32) -> u32 {
 fn radius_to_quarter_vertex_count(radius: f32) -> u32 {
     let scaled = radius.powf(0.8);
     (scaled / 4.0 ) as u32 + 8
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test {
+    use wgpu::TextureFormat;
+    use crate::{Color, Vec2, DrawCommand, RawCorners, RawSides, BorderColor};
+    use crate::wgpu::WGPUPainter;
+
+    #[test]
+    fn test_premultiplied_vertices() {
+        let mut painter = WGPUPainter::new(Vec2::splat(100.0), TextureFormat::Rgba8Unorm, 4096);
+        let color = Color::new(1.0, 0.5, 0.0, 0.5);
+        painter.paint(DrawCommand::Color(color));
+        painter.paint(DrawCommand::Rect { size: Vec2::splat(10.0) });
+        painter.paint(DrawCommand::Border {
+            size: Vec2::splat(10.0),
+            corners: RawCorners::default(),
+            widths: RawSides { top: 1.0, right: 1.0, bottom: 1.0, left: 1.0 },
+            colors: BorderColor::all(color)
+        });
+        assert!(painter.mesh.vertices.len() > 4);
+        assert!(painter.mesh.vertices.iter().all(|vertex| vertex.color == color.premultiply()));
+    }
+}
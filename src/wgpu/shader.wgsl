@@ -2,31 +2,95 @@ struct View {
     proj_view: mat4x4<f32>
 }
 
+struct Brush {
+    kind: u32,
+    stop_start: u32,
+    stop_count: u32,
+    start: vec2<f32>,
+    end: vec2<f32>
+}
+
+struct Stop {
+    color: vec4<f32>,
+    offset: f32
+}
+
 @group(0) @binding(0)
 var<uniform> view: View;
 
+@group(1) @binding(0)
+var<storage, read> brushes: array<Brush>;
+
+@group(1) @binding(1)
+var<storage, read> stops: array<Stop>;
+
+@group(2) @binding(0)
+var atlas: texture_2d<f32>;
+
+@group(2) @binding(1)
+var atlas_sampler: sampler;
+
 struct VertexInput {
     @location(0) position: vec2<f32>,
-    @location(1) color: vec4<f32>
+    @location(1) color: vec4<f32>,
+    @location(2) brush: u32,
+    @location(3) uv: vec2<f32>
 };
 
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
-    @location(0) color: vec4<f32>
+    @location(0) color: vec4<f32>,
+    @location(1) @interpolate(flat) brush: u32,
+    @location(2) world_position: vec2<f32>,
+    @location(3) uv: vec2<f32>
 };
 
 struct FragmentInput {
-    @location(0) color: vec4<f32>
+    @location(0) color: vec4<f32>,
+    @location(1) @interpolate(flat) brush: u32,
+    @location(2) world_position: vec2<f32>,
+    @location(3) uv: vec2<f32>
 }
 
 @vertex
 fn vert_main(input: VertexInput) -> VertexOutput {
     let out_pos = view.proj_view * vec4<f32>(input.position, 0.0, 1.0);
-    let out_color = input.color;
-    return VertexOutput(out_pos, out_color);
+    return VertexOutput(out_pos, input.color, input.brush, input.position, input.uv);
 }
 
 @fragment
 fn frag_main(in: FragmentInput) -> @location(0) vec4<f32> {
-    return in.color;
-}
\ No newline at end of file
+    // UVs are in texels. Untextured shapes sample a fully covered texel.
+    let coverage = textureSample(atlas, atlas_sampler, in.uv / vec2<f32>(textureDimensions(atlas))).r;
+    if in.brush == 0u {
+        return in.color * coverage;
+    }
+    let brush = brushes[in.brush];
+    var t: f32;
+    if brush.kind == 1u {
+        let line = brush.end - brush.start;
+        t = dot(in.world_position - brush.start, line) / max(dot(line, line), 0.0001);
+    }
+    else {
+        t = length((in.world_position - brush.start) / max(brush.end, vec2<f32>(0.0001)));
+    }
+    return sample_stops(brush, t) * in.color * coverage;
+}
+
+// Samples the color of a brush's stops at t.
+fn sample_stops(brush: Brush, t: f32) -> vec4<f32> {
+    if brush.stop_count == 0u {
+        return vec4<f32>(0.0);
+    }
+    let t = clamp(t, 0.0, 1.0);
+    var color = stops[brush.stop_start].color;
+    for (var i = 1u; i < brush.stop_count; i++) {
+        let a = stops[brush.stop_start + i - 1u];
+        let b = stops[brush.stop_start + i];
+        if t >= a.offset {
+            let span = max(b.offset - a.offset, 0.0001);
+            color = mix(a.color, b.color, clamp((t - a.offset) / span, 0.0, 1.0));
+        }
+    }
+    return color;
+}
@@ -14,7 +14,7 @@ impl View {
 
     pub fn new(size: Vec2, translation: Vec2, scale: f32) -> Self {
         let hw = size.x / 2.0;
-        let hh = size.y as f32 / 2.0;
+        let hh = size.y / 2.0;
         let proj_view =
             Mat4::orthographic_rh(-hw, hw, -hh, hh, 0.0, 1.0) *
             Mat4::from_scale(Vec3::new(1.0, -1.0, 1.0)) *
@@ -20,11 +20,11 @@ impl Widget for RadioButton {
             ctl.set_cursor_icon(CursorIcon::Default);
             ctl.stop();
         }
-        else if ctl.is_event::<PressEvent>() {
+        else if ctl.as_event::<PressEvent>().is_some_and(|event| event.button == MouseButton::Left) {
             ctl.press();
             ctl.stop();
         }
-        else if ctl.is_event::<ReleaseEvent>() {
+        else if ctl.as_event::<ReleaseEvent>().is_some_and(|event| event.button == MouseButton::Left) {
             self.selected = !self.selected;
             ctl.stop();
             ctl.repaint();
@@ -1,10 +1,16 @@
 mod button;
+mod text;
+mod rich_text;
+mod text_field;
 pub use button::*;
+pub use text::*;
+pub use rich_text::*;
+pub use text_field::*;
 
 use std::fmt::Debug;
 use std::any::Any;
 use crate::Vec2;
-use crate::{NodeId, Gewy, Node, Result, Painter, Style, RawCorners, GewyError, EventControl, Name, util};
+use crate::{NodeId, Gewy, Node, Result, Painter, Style, RawCorners, RawBorder, RawPadding, Rect, GewyError, EventControl, Name, util};
 
 
 /// Represents the type, state and rendering code of a [`crate::Node`].
@@ -22,6 +28,19 @@ pub trait Widget: Any + 'static {
         Ok(())
     }
 
+    /// Handles an event fired on a descendant before the descendant does, in the [`crate::EventPhase::Capture`] phase.
+    /// Stopping the event here keeps it from reaching the descendant.
+    fn capture_event(&mut self, _style: &mut Style, _descendants: &mut Descendants, _ctl: &mut EventControl) -> Result<()> {
+        Ok(())
+    }
+
+    /// Size of the widget's content, if it has any.
+    /// Used in place of the width or height of its style when they are auto.
+    /// The maximum size is the size of the parent's content.
+    fn measure(&self, _style: &Style, _max_size: Vec2) -> Option<Vec2> {
+        None
+    }
+
     /// Paints self.
     /// Invoked at rendering time.
     fn paint(&self, _style: &Style, _painter: &mut Painter, _canvas: Canvas) {}
@@ -56,7 +75,7 @@ impl<'n> Descendants<'n> {
     }
 
     /// Inserts a node and inherits the ancestor.
-    pub fn insert(&mut self, mut node: Node) -> Descendants {
+    pub fn insert(&mut self, mut node: Node) -> Descendants<'_> {
         node.ancestor_id = Some(self.ancestor_id);
         let parent_id = self.gewy.insert(self.parent_id, node).unwrap();
         Descendants { 
@@ -67,7 +86,7 @@ impl<'n> Descendants<'n> {
     }
 
     /// Inserts a node which becomes the ancestor of its children.
-    pub fn insert_ancestor(&mut self, mut node: Node) -> Descendants {
+    pub fn insert_ancestor(&mut self, mut node: Node) -> Descendants<'_> {
         node.ancestor_id = Some(self.ancestor_id);
         let parent_id = self.gewy.insert(self.parent_id, node).unwrap();
         Descendants { 
@@ -121,11 +140,24 @@ pub struct Canvas {
     /// Widgets must not paint outside of the range [0.0, 0.0] - [size.x, size.y].
     pub size: Vec2,
     /// Raw radiuses of corners in pixels.
-    pub corners: RawCorners
+    pub corners: RawCorners,
+    /// Raw widths of the border in pixels.
+    /// The border is painted inside of the canvas, along its edges.
+    pub border: RawBorder,
+    /// Raw widths of the padding in pixels, inside of the border.
+    pub padding: RawPadding
 }
 
 impl Canvas {
     pub fn center(&self) -> Vec2 {
         self.size / 2.0
     }
+
+    /// Region inside of the border and padding, where content is painted.
+    pub fn content_region(&self) -> Rect {
+        let (border, padding) = (&self.border, &self.padding);
+        let top_left = Vec2::new(border.left() + padding.left(), border.top() + padding.top());
+        let size = self.size - border.size() - padding.size();
+        Rect { position: top_left, size: size.max(Vec2::ZERO) }
+    }
 }
\ No newline at end of file
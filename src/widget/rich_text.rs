@@ -0,0 +1,57 @@
+use std::cell::RefCell;
+use crate::*;
+
+/// A widget that paints a [`Paragraph`], wrapped to the width of its content region.
+/// Spans without a color are painted in the color of its style.
+/// When the height of its style is auto, it is measured from the wrapped text.
+#[derive(Clone, Debug)]
+pub struct RichText {
+    paragraph: Paragraph,
+    layout: RefCell<Option<(f32, ParagraphLayout)>>
+}
+
+impl RichText {
+
+    pub fn new(paragraph: Paragraph) -> Self {
+        Self { paragraph, layout: RefCell::new(None) }
+    }
+
+    pub fn paragraph(&self) -> &Paragraph { &self.paragraph }
+
+    /// Replaces the paragraph, discarding its previous layout.
+    pub fn set_paragraph(&mut self, paragraph: Paragraph) {
+        self.paragraph = paragraph;
+        self.layout.take();
+    }
+
+    /// Lays out the paragraph, reusing the previous layout if the width changed by less than a pixel.
+    /// This keeps painting, where regions may be rounded to pixels, from wrapping differently than measuring.
+    pub fn layout(&self, max_width: f32) -> ParagraphLayout {
+        let mut cached = self.layout.borrow_mut();
+        match &*cached {
+            Some((width, layout)) if (width - max_width).abs() < 1.0 => layout.clone(),
+            _ => {
+                let layout = self.paragraph.layout(max_width);
+                *cached = Some((max_width, layout.clone()));
+                layout
+            }
+        }
+    }
+}
+
+impl Widget for RichText {
+
+    fn measure(&self, _style: &Style, max_size: Vec2) -> Option<Vec2> {
+        Some(self.layout(max_size.x).size)
+    }
+
+    fn paint(&self, style: &Style, painter: &mut Painter, canvas: Canvas) {
+        let content = canvas.content_region();
+        painter.move_to(content.position);
+        for (run, color) in self.layout(content.size.x).runs {
+            painter
+                .set_color(color.unwrap_or(style.color))
+                .paint_glyphs(run);
+        }
+    }
+}
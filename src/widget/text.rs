@@ -0,0 +1,44 @@
+use crate::*;
+
+/// A widget that paints a single run of text in the color of its style.
+/// Its size is measured from the text, when the width or height of its style is auto.
+#[derive(Clone, Debug)]
+pub struct Text {
+    run: GlyphRun,
+    text: String
+}
+
+impl Text {
+
+    pub fn new(text: impl Into<String>, font: Font, size: f32) -> Self {
+        let text = text.into();
+        Self {
+            run: GlyphRun::new(&text, font, size),
+            text
+        }
+    }
+
+    pub fn text(&self) -> &str { &self.text }
+    pub fn font(&self) -> &Font { &self.run.font }
+    pub fn size(&self) -> f32 { self.run.size }
+
+    /// Replaces the text, and lays it out again.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        self.run = GlyphRun::new(&self.text, self.run.font.clone(), self.run.size);
+    }
+}
+
+impl Widget for Text {
+
+    fn measure(&self, _style: &Style, _max_size: Vec2) -> Option<Vec2> {
+        Some(self.run.bounds)
+    }
+
+    fn paint(&self, style: &Style, painter: &mut Painter, canvas: Canvas) {
+        painter
+            .move_to(canvas.content_region().position)
+            .set_color(style.color)
+            .paint_glyphs(self.run.clone());
+    }
+}
@@ -0,0 +1,478 @@
+use std::cell::{Cell, RefCell};
+use crate::*;
+
+const EPS: f32 = 0.001;
+const CARET_WIDTH: f32 = 1.0;
+const PLACEHOLDER_ALPHA: f32 = 0.5;
+const SELECTION_COLOR: Color = Color::LIGHT_BLUE;
+
+/// A widget for editing text, either on a single line that scrolls horizontally, or on wrapped lines.
+///
+/// Is focusable, and fires a [`TextChangedEvent`] whenever the user changes its text.
+/// The background is painted in the color of its style, and the text in its text color.
+#[derive(Clone, Debug)]
+pub struct TextField {
+    editor: TextEditor,
+    font: Font,
+    size: f32,
+    text_color: Color,
+    placeholder: String,
+    mask: Option<char>,
+    rows: usize,
+    focused: bool,
+    dragging: bool,
+    // Horizontal position kept while moving up and down between lines.
+    preferred_x: Option<f32>,
+    layout: RefCell<Option<(f32, ParagraphLayout)>>,
+    // Content region and horizontal scroll of the last paint, used to hit the text with the cursor.
+    content: Cell<Rect>,
+    scroll: Cell<f32>
+}
+
+impl TextField {
+
+    /// Single-line text field.
+    pub fn new(text: impl Into<String>, font: Font, size: f32) -> Self {
+        Self {
+            editor: TextEditor::new(text),
+            font,
+            size,
+            text_color: Color::BLACK,
+            placeholder: String::new(),
+            mask: None,
+            rows: 1,
+            focused: false,
+            dragging: false,
+            preferred_x: None,
+            layout: RefCell::new(None),
+            content: Cell::new(Rect::default()),
+            scroll: Cell::new(0.0)
+        }
+    }
+
+    /// Wraps text and allows line breaks, measuring at least the number of rows specified in height.
+    pub fn with_multi_line(mut self, rows: usize) -> Self {
+        self.editor = self.editor.with_multi_line(true);
+        self.rows = rows.max(1);
+        self.layout.take();
+        self
+    }
+
+    /// Text shown in a dimmed text color while the text is empty.
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Shows every character of the text as a bullet.
+    pub fn with_password(self) -> Self {
+        self.with_mask('•')
+    }
+
+    /// Shows every character of the text as the mask character.
+    pub fn with_mask(mut self, mask: char) -> Self {
+        self.mask = Some(mask);
+        self.layout.take();
+        self
+    }
+
+    pub fn with_text_color(mut self, text_color: Color) -> Self {
+        self.text_color = text_color;
+        self
+    }
+
+    pub fn text(&self) -> &str { self.editor.text() }
+    pub fn editor(&self) -> &TextEditor { &self.editor }
+    pub fn placeholder(&self) -> &str { &self.placeholder }
+    pub fn is_focused(&self) -> bool { self.focused }
+
+    /// Replaces the text, without firing a [`TextChangedEvent`].
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.editor.set_text(text);
+        self.layout.take();
+    }
+
+    /// Text as it is displayed, with masked characters.
+    fn display_text(&self) -> String {
+        match self.mask {
+            Some(mask) => self.editor.text().chars().map(|_| mask).collect(),
+            None => self.editor.text().to_owned()
+        }
+    }
+
+    // Converts an offset of the text to an offset of the displayed text.
+    fn display_offset(&self, byte: usize) -> usize {
+        match self.mask {
+            Some(mask) => self.editor.text()[..byte].chars().count() * mask.len_utf8(),
+            None => byte
+        }
+    }
+
+    // Converts an offset of the displayed text to an offset of the text.
+    fn text_offset(&self, byte: usize) -> usize {
+        let text = self.editor.text();
+        match self.mask {
+            Some(mask) => text.char_indices().nth(byte / mask.len_utf8()).map_or(text.len(), |(i, _)| i),
+            None => byte
+        }
+    }
+
+    /// Lays out the displayed text, wrapped to a width if multi-line.
+    /// Reuses the previous layout if the width changed by less than a pixel.
+    fn layout(&self, width: f32) -> ParagraphLayout {
+        let mut cached = self.layout.borrow_mut();
+        match &*cached {
+            Some((cached_width, layout)) if (cached_width - width).abs() < 1.0 => layout.clone(),
+            _ => {
+                let max_width = if self.editor.multi_line() { width } else { f32::INFINITY };
+                let layout = Paragraph::new(self.display_text(), self.font.clone(), self.size).layout(max_width);
+                *cached = Some((width, layout.clone()));
+                layout
+            }
+        }
+    }
+
+    // Offset of the text under a point of the canvas.
+    fn hit(&self, point: Vec2) -> usize {
+        let content = self.content.get();
+        let layout = self.layout(content.size.x);
+        let point = point - content.position + Vec2::new(self.scroll.get(), 0.0);
+        self.text_offset(layout.hit(point))
+    }
+
+    fn key_down(&mut self, key: Key, modifiers: Modifiers, ctl: &mut EventControl) {
+        let extend = modifiers.shift();
+        let word = modifiers.control() || modifiers.alt();
+        let layout = self.layout(self.content.get().size.x);
+        let caret = self.display_offset(self.editor.caret());
+        let line = &layout.lines[layout.line_index(caret)];
+        let mut changed = false;
+        let mut preferred_x = None;
+        match key {
+
+            // Arrows move visually, which is backwards in right-to-left lines.
+            Key::Left if line.rtl => self.editor.move_right(word, extend),
+            Key::Left => self.editor.move_left(word, extend),
+            Key::Right if line.rtl => self.editor.move_left(word, extend),
+            Key::Right => self.editor.move_right(word, extend),
+            Key::Up | Key::Down if self.editor.multi_line() => {
+                let x = self.preferred_x.unwrap_or(layout.caret(caret).position.x);
+                let index = layout.line_index(caret);
+                let target = if key == Key::Up { index.checked_sub(1) } else { Some(index + 1) };
+                let byte = match target.and_then(|index| layout.lines.get(index)) {
+                    Some(line) => self.text_offset(layout.hit(Vec2::new(x, line.top + line.height / 2.0))),
+                    None if key == Key::Up => 0,
+                    None => self.editor.text().len()
+                };
+                self.editor.set_caret(byte, extend);
+                preferred_x = Some(x);
+            },
+            Key::Up => self.editor.set_caret(0, extend),
+            Key::Down => self.editor.set_caret(self.editor.text().len(), extend),
+            Key::Home if modifiers.command() => self.editor.set_caret(0, extend),
+            Key::End if modifiers.command() => self.editor.set_caret(self.editor.text().len(), extend),
+            Key::Home => self.editor.set_caret(self.text_offset(line.range.start), extend),
+            Key::End => {
+
+                // Stays before line breaks, and before the whitespace that a wrapped line ends with.
+                let display = self.display_text();
+                let mut end = line.range.end;
+                let is_last = line.range.end == display.len();
+                if let Some(c) = display[line.range.start..end].chars().next_back() {
+                    if c == '\n' || (!is_last && c.is_whitespace()) {
+                        end -= c.len_utf8();
+                    }
+                }
+                self.editor.set_caret(self.text_offset(end), extend);
+            },
+            Key::Backspace => changed = self.editor.delete_backward(word),
+            Key::Delete => changed = self.editor.delete_forward(word),
+            Key::Enter if self.editor.multi_line() => changed = self.editor.insert("\n"),
+            Key::A if modifiers.command() => self.editor.select_all(),
+            Key::Z if modifiers.command() && modifiers.shift() => changed = self.editor.redo(),
+            Key::Z if modifiers.command() => changed = self.editor.undo(),
+            Key::Y if modifiers.command() => changed = self.editor.redo(),
+
+            // Lets other keys bubble up, like tab and escape.
+            _ => return
+        }
+        self.preferred_x = preferred_x;
+        if changed {
+            self.changed(ctl);
+        }
+        ctl.repaint();
+        ctl.stop();
+    }
+
+    fn changed(&mut self, ctl: &mut EventControl) {
+        self.layout.take();
+        ctl.fire(TextChangedEvent { text: self.editor.text().to_owned() });
+    }
+}
+
+impl Widget for TextField {
+
+    fn style(&self, style: &mut Style) {
+        style.width = Val::Px(200.0);
+        style.color = Color::WHITE;
+        style.padding = Sides::new(Val::Px(4.0), Val::Px(6.0), Val::Px(4.0), Val::Px(6.0));
+        style.border = Border::all(Val::Px(1.0), Color::GRAY);
+        style.corners = Corners::all(Val::Px(3.0));
+        style.focusable = true;
+    }
+
+    fn event(&mut self, _style: &mut Style, _descendants: &mut Descendants, ctl: &mut EventControl) -> Result<()> {
+        if ctl.is_event::<EnterEvent>() {
+            ctl.set_cursor_icon(CursorIcon::Text);
+            ctl.stop();
+        }
+        else if ctl.is_event::<ExitEvent>() {
+            ctl.set_cursor_icon(CursorIcon::Default);
+            ctl.stop();
+        }
+        else if ctl.as_event::<PressEvent>().is_some_and(|event| event.button == MouseButton::Left) {
+            let byte = self.hit(ctl.cursor_position());
+            self.editor.set_caret(byte, false);
+            self.dragging = true;
+            self.preferred_x = None;
+            ctl.capture();
+            ctl.repaint();
+            ctl.stop();
+        }
+        else if ctl.is_event::<PointerMoveEvent>() {
+            if self.dragging {
+                let byte = self.hit(ctl.cursor_position());
+                self.editor.set_caret(byte, true);
+                ctl.repaint();
+            }
+            ctl.stop();
+        }
+        else if ctl.is_event::<ReleaseEvent>() {
+            self.dragging = false;
+            ctl.stop();
+        }
+        else if ctl.is_event::<FocusEvent>() {
+            self.focused = true;
+            ctl.repaint();
+            ctl.stop();
+        }
+        else if ctl.is_event::<UnfocusEvent>() {
+            self.focused = false;
+            self.dragging = false;
+            ctl.repaint();
+            ctl.stop();
+        }
+        else if let Some(event) = ctl.as_event::<KeyDownEvent>() {
+            let KeyDownEvent { key, modifiers } = *event;
+            self.key_down(key, modifiers, ctl);
+        }
+        else if let Some(event) = ctl.as_event::<CharInputEvent>() {
+
+            // Control characters, like backspace and enter, are handled as keys.
+            let text: String = event.text.chars().filter(|c| !c.is_control()).collect();
+            if !text.is_empty() && self.editor.insert(&text) {
+                self.preferred_x = None;
+                self.changed(ctl);
+                ctl.repaint();
+            }
+            ctl.stop();
+        }
+        Ok(())
+    }
+
+    fn measure(&self, _style: &Style, max_size: Vec2) -> Option<Vec2> {
+        let layout = self.layout(max_size.x);
+        let rows_height = self.font.metrics(self.size).line_height() * self.rows as f32;
+        Some(Vec2::new(layout.size.x, layout.size.y.max(rows_height)))
+    }
+
+    fn paint(&self, style: &Style, painter: &mut Painter, canvas: Canvas) {
+        util::paint_pane(style, painter, canvas);
+        let content = canvas.content_region();
+        self.content.set(content);
+        let layout = self.layout(content.size.x);
+        let caret = layout.caret(self.display_offset(self.editor.caret()));
+
+        // Scrolls single lines to keep the caret in view.
+        let mut scroll = 0.0;
+        if !self.editor.multi_line() {
+            scroll = self.scroll.get();
+            let caret_x = caret.position.x;
+            if caret_x < scroll {
+                scroll = caret_x;
+            }
+            else if caret_x + CARET_WIDTH > scroll + content.size.x {
+                scroll = caret_x + CARET_WIDTH - content.size.x;
+            }
+            scroll = scroll.min(layout.size.x + CARET_WIDTH - content.size.x).max(0.0);
+        }
+        self.scroll.set(scroll);
+        let origin = content.position - Vec2::new(scroll, 0.0);
+        let visible = |left: f32, right: f32| left >= scroll - EPS && right <= scroll + content.size.x + EPS;
+
+        // Paints the selection, clipped to the content region.
+        let selection = self.editor.selection();
+        let selection = self.display_offset(selection.start)..self.display_offset(selection.end);
+        painter.set_color(SELECTION_COLOR);
+        for rect in layout.selection(selection) {
+            let left = rect.position.x.max(scroll);
+            let right = (rect.position.x + rect.size.x).min(scroll + content.size.x);
+            if right <= left { continue }
+            painter
+                .move_to(origin + Vec2::new(left, rect.position.y))
+                .paint_rect(Vec2::new(right - left, rect.size.y));
+        }
+
+        // Paints the text, or the placeholder, skipping glyphs scrolled out of view.
+        let (layout, color) = if self.editor.text().is_empty() && !self.placeholder.is_empty() {
+            let placeholder = Paragraph::new(self.placeholder.as_str(), self.font.clone(), self.size).layout(content.size.x);
+            (placeholder, self.text_color.with_alpha(self.text_color.a() * PLACEHOLDER_ALPHA))
+        }
+        else {
+            (layout, self.text_color)
+        };
+        painter.move_to(origin).set_color(color);
+        for (mut run, _) in layout.runs {
+            run.glyphs.retain(|glyph| {
+                let advance = run.font.advance(glyph.id, run.size);
+                visible(glyph.position.x, glyph.position.x + advance)
+            });
+            painter.paint_glyphs(run);
+        }
+
+        // Paints the caret.
+        if self.focused && visible(caret.position.x, caret.position.x + CARET_WIDTH) {
+            painter
+                .move_to(origin + caret.position)
+                .set_color(self.text_color)
+                .paint_rect(Vec2::new(CARET_WIDTH, caret.size.y));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::event::test::{EventLog, Recorder, logged};
+    use crate::text::test::font;
+    use crate::*;
+
+    // Form that records every TextChangedEvent fired by its text field.
+    fn form(field: TextField) -> (Gewy, NodeId, EventLog) {
+        let log = EventLog::default();
+        let form = Recorder::new("form", &log)
+            .with::<TextChangedEvent>()
+            .with_descendants(move |descendants| { dsl::text_field((), field.clone(), descendants); });
+        let mut gewy = Gewy::new(Node::default());
+        let form_id = gewy.insert(gewy.root_id(), Node::from_widget(form)).unwrap();
+        let field_id = gewy.get(form_id).unwrap().children()[0];
+        gewy.resize(Vec2::new(400.0, 400.0));
+        gewy.paint();
+        (gewy, field_id, log)
+    }
+
+    fn field(gewy: &Gewy, field_id: NodeId) -> &TextField {
+        let widget: &dyn std::any::Any = gewy.get(field_id).unwrap().widget();
+        widget.downcast_ref().unwrap()
+    }
+
+    #[test]
+    fn test_typing() {
+        let (mut gewy, field_id, log) = form(TextField::new("", font(), 20.0));
+
+        // Receives input once focused by a press.
+        gewy.mapping().char_input("x").unwrap();
+        assert_eq!("", field(&gewy, field_id).text());
+        let center = gewy.get(field_id).unwrap().raw.border_region().center();
+        let mut mapping = gewy.mapping();
+        mapping.move_cursor(center).unwrap();
+        mapping.press(MouseButton::Left).unwrap();
+        mapping.release(MouseButton::Left).unwrap();
+        assert_eq!(Some(field_id), gewy.focused_id());
+        assert!(field(&gewy, field_id).is_focused());
+
+        let mut mapping = gewy.mapping();
+        mapping.char_input("hello world").unwrap();
+        mapping.key_down(Key::Backspace, Modifiers::CONTROL).unwrap();
+        mapping.key_down(Key::Left, Modifiers::SHIFT).unwrap();
+        mapping.char_input("\r").unwrap();
+        mapping.key_down(Key::Enter, Modifiers::empty()).unwrap();
+        mapping.char_input("p").unwrap();
+        assert_eq!("hellop", field(&gewy, field_id).text());
+        let changed = |text: &str| logged(("form", TextChangedEvent { text: text.into() }));
+        assert_eq!(vec![changed("hello world"), changed("hello "), changed("hellop")], log.take());
+
+        // Undoes edits.
+        gewy.mapping().key_down(Key::Z, Modifiers::CONTROL | Modifiers::SUPER).unwrap();
+        assert_eq!("hello ", field(&gewy, field_id).text());
+    }
+
+    #[test]
+    fn test_selection() {
+        let font = font();
+        let (mut gewy, field_id, _) = form(TextField::new("one two", font.clone(), 20.0).with_password());
+        let region = gewy.get(field_id).unwrap().raw.content_region();
+        let bullet = font.advance(font.glyph_id('•'), 20.0);
+
+        // Drags across masked characters.
+        let y = region.center().y;
+        let mut mapping = gewy.mapping();
+        mapping.move_cursor(Vec2::new(region.position.x + bullet * 1.1, y)).unwrap();
+        mapping.press(MouseButton::Left).unwrap();
+        mapping.move_cursor(Vec2::new(region.position.x + bullet * 3.9, y)).unwrap();
+        assert_eq!(1..4, field(&gewy, field_id).editor().selection());
+        let [selected] = selection_rects(gewy.paint())[..] else { panic!("Expected one selection rect") };
+        assert!((selected.position.x - (region.position.x + bullet)).abs() < 0.01);
+        assert!((selected.size.x - bullet * 3.0).abs() < 0.01);
+
+        // Extends the selection with the keyboard, and replaces it with typed text.
+        let mut mapping = gewy.mapping();
+        mapping.release(MouseButton::Left).unwrap();
+        mapping.key_down(Key::End, Modifiers::SHIFT).unwrap();
+        mapping.char_input("n").unwrap();
+        assert_eq!("on", field(&gewy, field_id).text());
+        assert!(selection_rects(gewy.paint()).is_empty());
+    }
+
+    // Rects painted with the selection color, at their translations.
+    fn selection_rects(commands: Vec<DrawCommand>) -> Vec<Rect> {
+        let (mut selecting, mut position) = (false, Vec2::ZERO);
+        let mut rects = Vec::new();
+        for command in commands {
+            match command {
+                DrawCommand::Color(color) => selecting = color == super::SELECTION_COLOR,
+                DrawCommand::Brush(_) => selecting = false,
+                DrawCommand::Translation(translation) => position = translation,
+                DrawCommand::Rect { size } if selecting => rects.push(Rect::new(position, size)),
+                _ => {}
+            }
+        }
+        rects
+    }
+
+    #[test]
+    fn test_multi_line() {
+        let (mut gewy, field_id, _) = form(TextField::new("", font(), 20.0).with_multi_line(3));
+        let line_height = font().metrics(20.0).line_height();
+        assert!(gewy.get(field_id).unwrap().raw.content_region().size.y >= 3.0 * line_height);
+
+        let center = gewy.get(field_id).unwrap().raw.border_region().center();
+        let mut mapping = gewy.mapping();
+        mapping.move_cursor(center).unwrap();
+        mapping.press(MouseButton::Left).unwrap();
+        mapping.char_input("abc").unwrap();
+        mapping.key_down(Key::Enter, Modifiers::empty()).unwrap();
+        mapping.char_input("d").unwrap();
+        assert_eq!("abc\nd", field(&gewy, field_id).text());
+
+        // Moves between lines, and to their ends.
+        let mut mapping = gewy.mapping();
+        mapping.key_down(Key::Up, Modifiers::empty()).unwrap();
+        assert_eq!(1, field(&gewy, field_id).editor().caret());
+        let mut mapping = gewy.mapping();
+        mapping.key_down(Key::End, Modifiers::empty()).unwrap();
+        assert_eq!(3, field(&gewy, field_id).editor().caret());
+        let mut mapping = gewy.mapping();
+        mapping.key_down(Key::Down, Modifiers::SHIFT).unwrap();
+        assert_eq!(3..5, field(&gewy, field_id).editor().selection());
+    }
+}
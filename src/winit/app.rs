@@ -1,12 +1,13 @@
+use std::time::Duration;
 use crate::Vec2;
 use wgpu::*;
 use winit::window::{WindowBuilder, CursorIcon};
-use winit::event::{Event, WindowEvent, KeyboardInput, ElementState, VirtualKeyCode, MouseButton};
+use winit::event::{Event, WindowEvent, KeyboardInput, ElementState, VirtualKeyCode, ModifiersState, MouseButton, MouseScrollDelta};
 use winit::event_loop::{EventLoop, ControlFlow};
 use winit::dpi::PhysicalSize;
 use winit::window::Window;
 use crate::wgpu::WgpuBackend;
-use crate::Gewy;
+use crate::{Gewy, StyleSheetWatcher, Key, Modifiers, ScrollDelta, Clock, SystemClock};
 
 /// A configurable application runner that runs a single [`Gewy`] in a single [`winit`] window.
 pub struct WinitApp {
@@ -16,6 +17,10 @@ pub struct WinitApp {
     pub backend_kind: BackendKind,
     pub debug: bool,
     pub samples_per_pixel: u32,
+    /// Closes the window when escape is pressed, after the [`Gewy`] receives it.
+    pub exit_on_escape: bool,
+    /// Reloads the style sheet of the [`Gewy`] when its file changes.
+    pub style_sheet_watcher: Option<StyleSheetWatcher>
 }
 
 /// Stores the application in a window.
@@ -28,7 +33,9 @@ impl WinitApp {
             backend_kind,
             gewy,
             debug: false,
-            samples_per_pixel: 8
+            samples_per_pixel: 8,
+            exit_on_escape: true,
+            style_sheet_watcher: None
         }
     }
 
@@ -47,10 +54,20 @@ impl WinitApp {
         self
     }
 
+    pub fn with_exit_on_escape(mut self, exit_on_escape: bool) -> Self {
+        self.exit_on_escape = exit_on_escape;
+        self
+    }
+
+    pub fn with_style_sheet_watcher(mut self, watcher: StyleSheetWatcher) -> Self {
+        self.style_sheet_watcher = Some(watcher);
+        self
+    }
+
     pub async fn start(self) -> ! {
         
         // Opens window and handle high-level events
-        let Self { width, height, gewy, debug, samples_per_pixel, backend_kind } = self;
+        let Self { width, height, gewy, debug, samples_per_pixel, backend_kind, exit_on_escape, mut style_sheet_watcher } = self;
         let size = PhysicalSize::new(width, height);
         let event_loop = EventLoop::new();
         let winit_window = WindowBuilder::new()
@@ -60,18 +77,28 @@ impl WinitApp {
         let mut window = GewyWindow::new(winit_window, gewy, debug, samples_per_pixel, backend_kind).await;
 
         // Runs event loop
+        let clock = SystemClock::new();
         event_loop.run(move |event, _, flow| {
             match event {
-                Event::WindowEvent { event, .. } => Self::handle_window_event(event, &mut window, flow),
+                Event::WindowEvent { event, .. } => {
+                    Self::handle_time(clock.now(), &mut window);
+                    Self::handle_window_event(event, &mut window, exit_on_escape, flow)
+                },
                 Event::RedrawRequested( .. ) => Self::handle_redraw_event(&mut window, flow),
-                Event::MainEventsCleared => { window.winit_window().request_redraw() }
+                Event::MainEventsCleared => {
+                    Self::handle_tick(clock.now(), &mut window);
+                    if let Some(watcher) = &mut style_sheet_watcher {
+                        Self::handle_style_sheet(watcher, &mut window);
+                    }
+                    window.winit_window().request_redraw()
+                }
                 _ => {}
             }
         });
     }
 
     // Handle window-related events.
-    fn handle_window_event(event: WindowEvent<'_>, window: &mut GewyWindow, flow: &mut ControlFlow) {
+    fn handle_window_event(event: WindowEvent<'_>, window: &mut GewyWindow, exit_on_escape: bool, flow: &mut ControlFlow) {
         if window.input(&event) { return }
         match event {
             WindowEvent::Resized(size) => window.resize(size),
@@ -84,11 +111,34 @@ impl WinitApp {
                     ..
                 },
                 ..
-            } => *flow = ControlFlow::Exit,
+            } if exit_on_escape => *flow = ControlFlow::Exit,
             _ => {}
         }
     }
 
+    // Advances the time of input, which may complete a long press.
+    fn handle_time(time: Duration, window: &mut GewyWindow) {
+        if let Err(err) = window.gewy_mut().mapping().set_time(time) {
+            eprintln!("Advancing time caused an error: {}", err);
+        }
+    }
+
+    // Ticks once per frame, advancing time and firing tick and timer events.
+    fn handle_tick(now: Duration, window: &mut GewyWindow) {
+        if let Err(err) = window.gewy_mut().tick(now) {
+            eprintln!("Ticking caused an error: {}", err);
+        }
+    }
+
+    // Reloads the style sheet if its file changed.
+    fn handle_style_sheet(watcher: &mut StyleSheetWatcher, window: &mut GewyWindow) {
+        match watcher.update(window.gewy_mut()) {
+            Ok(true) => log::info!("Reloaded style sheet {}", watcher.path().display()),
+            Ok(false) => {}
+            Err(err) => log::error!("Failed to load style sheet: {err}")
+        }
+    }
+
     // Handle redraw events events.
     fn handle_redraw_event(window: &mut GewyWindow, flow: &mut ControlFlow) {
         match window.render() {
@@ -151,16 +201,45 @@ impl GewyWindow {
                     eprintln!("WindowEvent::CursorMoved caused an error: {}", err);
                 }
             },
-            WindowEvent::MouseInput { state: ElementState::Pressed, button: MouseButton::Left, .. } => {
-                let result = gewy.mapping().press(crate::MouseButton::Left);
+            WindowEvent::MouseInput { state, button, .. } => {
+                let button = crate::MouseButton::from(*button);
+                let result = match state {
+                    ElementState::Pressed => gewy.mapping().press(button),
+                    ElementState::Released => gewy.mapping().release(button)
+                };
                 if let Err(err) = result {
                     eprintln!("WindowEvent::MouseInput caused an error: {}", err);
                 }
             },
-            WindowEvent::MouseInput { state: ElementState::Released, button: MouseButton::Left, .. } => {
-                let result = gewy.mapping().release(crate::MouseButton::Left);
+            WindowEvent::MouseWheel { delta, .. } => {
+                let delta = match *delta {
+                    MouseScrollDelta::LineDelta(x, y) => ScrollDelta::Lines(Vec2::new(x, y)),
+                    MouseScrollDelta::PixelDelta(position) => ScrollDelta::Pixels(Vec2::new(position.x as f32, position.y as f32))
+                };
+                let result = gewy.mapping().scroll(delta);
                 if let Err(err) = result {
-                    eprintln!("WindowEvent::MouseInput caused an error: {}", err);
+                    eprintln!("WindowEvent::MouseWheel caused an error: {}", err);
+                }
+            },
+            WindowEvent::ModifiersChanged(modifiers) => {
+                gewy.mapping().set_modifiers((*modifiers).into());
+            },
+            WindowEvent::KeyboardInput { input: KeyboardInput { state, virtual_keycode, .. }, .. } => {
+                let key = virtual_keycode.map_or(Key::Unknown, Key::from);
+                let mut mapping = gewy.mapping();
+                let modifiers = mapping.modifiers();
+                let result = match state {
+                    ElementState::Pressed => mapping.key_down(key, modifiers),
+                    ElementState::Released => mapping.key_up(key, modifiers)
+                };
+                if let Err(err) = result {
+                    eprintln!("WindowEvent::KeyboardInput caused an error: {}", err);
+                }
+            },
+            WindowEvent::ReceivedCharacter(c) => {
+                let result = gewy.mapping().char_input(c.to_string());
+                if let Err(err) = result {
+                    eprintln!("WindowEvent::ReceivedCharacter caused an error: {}", err);
                 }
             },
             _ => return false
@@ -178,6 +257,12 @@ impl GewyWindow {
         Ok(())
     }
 
+    fn gewy_mut(&mut self) -> &mut Gewy {
+        match self {
+            Self::WgpuWindow(wgpu_window) => &mut wgpu_window.gewy
+        }
+    }
+
     fn winit_window(&self) -> &Window {
         match self {
             Self::WgpuWindow(wgpu_window) => &wgpu_window.window
@@ -321,6 +406,102 @@ impl From<crate::CursorIcon> for CursorIcon {
     }
 }
 
+impl From<MouseButton> for crate::MouseButton {
+    fn from(value: MouseButton) -> Self {
+        match value {
+            MouseButton::Left => crate::MouseButton::Left,
+            MouseButton::Right => crate::MouseButton::Right,
+            MouseButton::Middle => crate::MouseButton::Middle,
+            MouseButton::Other(other) => crate::MouseButton::Other(other)
+        }
+    }
+}
+
+impl From<VirtualKeyCode> for Key {
+    fn from(value: VirtualKeyCode) -> Self {
+        match value {
+            VirtualKeyCode::A => Key::A,
+            VirtualKeyCode::B => Key::B,
+            VirtualKeyCode::C => Key::C,
+            VirtualKeyCode::D => Key::D,
+            VirtualKeyCode::E => Key::E,
+            VirtualKeyCode::F => Key::F,
+            VirtualKeyCode::G => Key::G,
+            VirtualKeyCode::H => Key::H,
+            VirtualKeyCode::I => Key::I,
+            VirtualKeyCode::J => Key::J,
+            VirtualKeyCode::K => Key::K,
+            VirtualKeyCode::L => Key::L,
+            VirtualKeyCode::M => Key::M,
+            VirtualKeyCode::N => Key::N,
+            VirtualKeyCode::O => Key::O,
+            VirtualKeyCode::P => Key::P,
+            VirtualKeyCode::Q => Key::Q,
+            VirtualKeyCode::R => Key::R,
+            VirtualKeyCode::S => Key::S,
+            VirtualKeyCode::T => Key::T,
+            VirtualKeyCode::U => Key::U,
+            VirtualKeyCode::V => Key::V,
+            VirtualKeyCode::W => Key::W,
+            VirtualKeyCode::X => Key::X,
+            VirtualKeyCode::Y => Key::Y,
+            VirtualKeyCode::Z => Key::Z,
+            VirtualKeyCode::Key0 | VirtualKeyCode::Numpad0 => Key::Digit0,
+            VirtualKeyCode::Key1 | VirtualKeyCode::Numpad1 => Key::Digit1,
+            VirtualKeyCode::Key2 | VirtualKeyCode::Numpad2 => Key::Digit2,
+            VirtualKeyCode::Key3 | VirtualKeyCode::Numpad3 => Key::Digit3,
+            VirtualKeyCode::Key4 | VirtualKeyCode::Numpad4 => Key::Digit4,
+            VirtualKeyCode::Key5 | VirtualKeyCode::Numpad5 => Key::Digit5,
+            VirtualKeyCode::Key6 | VirtualKeyCode::Numpad6 => Key::Digit6,
+            VirtualKeyCode::Key7 | VirtualKeyCode::Numpad7 => Key::Digit7,
+            VirtualKeyCode::Key8 | VirtualKeyCode::Numpad8 => Key::Digit8,
+            VirtualKeyCode::Key9 | VirtualKeyCode::Numpad9 => Key::Digit9,
+            VirtualKeyCode::F1 => Key::F1,
+            VirtualKeyCode::F2 => Key::F2,
+            VirtualKeyCode::F3 => Key::F3,
+            VirtualKeyCode::F4 => Key::F4,
+            VirtualKeyCode::F5 => Key::F5,
+            VirtualKeyCode::F6 => Key::F6,
+            VirtualKeyCode::F7 => Key::F7,
+            VirtualKeyCode::F8 => Key::F8,
+            VirtualKeyCode::F9 => Key::F9,
+            VirtualKeyCode::F10 => Key::F10,
+            VirtualKeyCode::F11 => Key::F11,
+            VirtualKeyCode::F12 => Key::F12,
+            VirtualKeyCode::Left => Key::Left,
+            VirtualKeyCode::Right => Key::Right,
+            VirtualKeyCode::Up => Key::Up,
+            VirtualKeyCode::Down => Key::Down,
+            VirtualKeyCode::Home => Key::Home,
+            VirtualKeyCode::End => Key::End,
+            VirtualKeyCode::PageUp => Key::PageUp,
+            VirtualKeyCode::PageDown => Key::PageDown,
+            VirtualKeyCode::Back => Key::Backspace,
+            VirtualKeyCode::Delete => Key::Delete,
+            VirtualKeyCode::Insert => Key::Insert,
+            VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => Key::Enter,
+            VirtualKeyCode::Tab => Key::Tab,
+            VirtualKeyCode::Escape => Key::Escape,
+            VirtualKeyCode::Space => Key::Space,
+            VirtualKeyCode::LShift | VirtualKeyCode::RShift => Key::Shift,
+            VirtualKeyCode::LControl | VirtualKeyCode::RControl => Key::Control,
+            VirtualKeyCode::LAlt | VirtualKeyCode::RAlt => Key::Alt,
+            VirtualKeyCode::LWin | VirtualKeyCode::RWin => Key::Super,
+            _ => Key::Unknown
+        }
+    }
+}
+
+impl From<ModifiersState> for Modifiers {
+    fn from(value: ModifiersState) -> Self {
+        let mut modifiers = Modifiers::empty();
+        modifiers.set(Modifiers::SHIFT, value.shift());
+        modifiers.set(Modifiers::CONTROL, value.ctrl());
+        modifiers.set(Modifiers::ALT, value.alt());
+        modifiers.set(Modifiers::SUPER, value.logo());
+        modifiers
+    }
+}
 
 fn features(debug: bool) -> Features {
     let mut features = Features::empty();